@@ -0,0 +1,82 @@
+//! Rule 9 (FIX 10 in examples/after.rs): imperative loop vs iterator chain
+//! for `process_items`.
+//!
+//! `process_items` (examples/before.rs) and `process_items_grit`
+//! (examples/after.rs) are private to their example binaries, so the two
+//! bodies are reproduced here verbatim, next to the library's
+//! `GritIterExt::filter_transform`. Run with `cargo bench --bench process_items`.
+//!
+//! Result when this was added (10k items, release build): the loop took
+//! about 380µs, the iterator chain about 510µs and `filter_transform` about
+//! 480µs. The iterator forms are within the same order of magnitude but
+//! measurably slower here, so Rule 9 rests on readability rather than
+//! speed; rerun on your own toolchain before citing numbers.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use grit::prelude::GritIterExt;
+
+struct Item {
+    name: String,
+}
+
+impl Item {
+    fn is_valid(&self) -> bool {
+        !self.name.is_empty()
+    }
+}
+
+fn process_items(items: &[Item]) -> Vec<String> {
+    let mut results = Vec::new();
+    for item in items {
+        if item.is_valid() {
+            results.push(item.name.clone());
+        }
+    }
+    results
+}
+
+fn process_items_grit(items: &[Item]) -> Vec<String> {
+    items
+        .iter()
+        .filter(|item| item.is_valid())
+        .map(|item| item.name.clone())
+        .collect()
+}
+
+fn process_items_fused(items: &[Item]) -> Vec<String> {
+    items
+        .iter()
+        .filter_transform(|item| item.is_valid(), |item| item.name.clone())
+        .collect()
+}
+
+// 10k items, every third one invalid
+fn items() -> Vec<Item> {
+    (0..10_000)
+        .map(|n| Item {
+            name: if n % 3 == 0 {
+                String::new()
+            } else {
+                n.to_string()
+            },
+        })
+        .collect()
+}
+
+fn bench_process_items(c: &mut Criterion) {
+    let items = items();
+    let mut group = c.benchmark_group("process_items");
+    group.bench_function("imperative", |b| {
+        b.iter(|| process_items(black_box(&items)))
+    });
+    group.bench_function("iterator", |b| {
+        b.iter(|| process_items_grit(black_box(&items)))
+    });
+    group.bench_function("filter_transform", |b| {
+        b.iter(|| process_items_fused(black_box(&items)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_process_items);
+criterion_main!(benches);
@@ -1,10 +1,24 @@
 // after.rs - Grit-compliant versions
 // These patterns follow Grit rules and eliminate the subtle bugs.
 
-use std::collections::HashMap;
-use std::io::BufWriter;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufWriter, Write};
+use std::marker::PhantomData;
 use std::net::TcpStream;
 
+// Reporting goes through `tracing` when the feature is enabled. Without it,
+// library code stays silent rather than printing to stdout. The shim sits
+// above every use because the `macro_rules!` fallback is textually scoped.
+#[cfg(feature = "tracing")]
+use tracing::info;
+#[cfg(not(feature = "tracing"))]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        ()
+    };
+}
+
 // --- FIX 1: Explicit Lifetimes ---
 // Grit: All public functions show lifetime relationships
 
@@ -22,6 +36,38 @@ pub fn parse<'a>(input: &'a str) -> Option<&'a str> {
     input.split(':').next()
 }
 
+/// Parses the first segment before any of `delims`.
+/// The returned reference borrows from the input string; with no
+/// delimiters the whole input is returned.
+pub fn parse_on<'a>(input: &'a str, delims: &[char]) -> Option<&'a str> {
+    input.split(delims).next()
+}
+
+/// Parses every ':'-separated segment.
+/// Each segment borrows from the input string; an empty input yields one
+/// empty segment.
+pub fn parse_segments<'a>(input: &'a str) -> impl Iterator<Item = &'a str> {
+    input.split(':')
+}
+
+/// Parses the first segment before ':', trimmed and lowercased.
+///
+/// Returns `Cow::Borrowed` when the segment is already normalized, and
+/// `Cow::Owned` when trimming or lowercasing changed it.
+pub fn parse_normalized<'a>(input: &'a str) -> Cow<'a, str> {
+    let segment = parse(input).unwrap_or_default();
+    let trimmed = segment.trim();
+    // Not `char::is_uppercase`: titlecase letters such as 'ǅ' also lowercase
+    let changes_case = trimmed
+        .chars()
+        .any(|c| c.to_lowercase().ne(std::iter::once(c)));
+    if trimmed.len() != segment.len() || changes_case {
+        Cow::Owned(trimmed.to_lowercase())
+    } else {
+        Cow::Borrowed(segment)
+    }
+}
+
 // --- FIX 2: Explicit Conversions ---
 // Grit: Never rely on implicit Deref
 
@@ -41,13 +87,40 @@ fn use_string_explicit() {
 // --- FIX 3: Error Propagation Instead of Panic ---
 // Grit: Library code propagates errors
 
-#[derive(Debug)]
+/// Errors returned by user lookups.
+///
+/// Guaranteed `Send + Sync`, so it can be returned from spawned tasks.
+#[derive(Debug, Clone)]
 pub enum UserError {
     NotFound,
     ParseError(String),
     EmptyCollection,
 }
 
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<UserError>();
+};
+
+impl UserError {
+    /// Builds a `ParseError` from any displayable message.
+    pub fn parse_error(msg: impl ToString) -> Self {
+        UserError::ParseError(msg.to_string())
+    }
+}
+
+impl From<String> for UserError {
+    fn from(msg: String) -> Self {
+        UserError::ParseError(msg)
+    }
+}
+
+impl From<&str> for UserError {
+    fn from(msg: &str) -> Self {
+        UserError::ParseError(msg.to_owned())
+    }
+}
+
 /// Gets a user by ID.
 ///
 /// # Errors
@@ -64,9 +137,7 @@ pub fn get_user<'a>(
 /// # Errors
 /// Returns `UserError::ParseError` if the input is not a valid number.
 pub fn parse_number(input: &str) -> Result<i32, UserError> {
-    input
-        .parse()
-        .map_err(|e| UserError::ParseError(format!("{}", e)))
+    input.parse().map_err(UserError::parse_error)
 }
 
 /// Gets the first item from a vector.
@@ -89,11 +160,106 @@ fn store_typed<T: Item>(item: T) {
     item.process();
 }
 
+// For a small, known set of item types, an enum replaces Box<dyn Any>:
+// the type stays concrete and matchable, with no downcasting.
+struct TextItem {
+    text: String,
+}
+
+struct ImageItem {
+    bytes: Vec<u8>,
+}
+
+impl Item for TextItem {
+    fn process(&self) {
+        info!(text = %self.text, "processing text item");
+    }
+}
+
+impl Item for ImageItem {
+    fn process(&self) {
+        info!(bytes = self.bytes.len(), "processing image item");
+    }
+}
+
+enum ItemKind {
+    Text(TextItem),
+    Image(ImageItem),
+}
+
+impl Item for ItemKind {
+    fn process(&self) {
+        // Delegate to the concrete type - every variant listed (Rule 7)
+        match self {
+            ItemKind::Text(item) => item.process(),
+            ItemKind::Image(item) => item.process(),
+        }
+    }
+}
+
+fn process_all(items: &[ItemKind]) {
+    items.iter().for_each(Item::process);
+}
+
+// Visitor: new operations over ItemKind without Any downcasting.
+// Adding a variant adds a Visitor method, so every visitor must handle it.
+trait Visitor {
+    fn visit_text(&mut self, item: &TextItem);
+    fn visit_image(&mut self, item: &ImageItem);
+}
+
+impl ItemKind {
+    fn accept<V: Visitor>(&self, visitor: &mut V) {
+        match self {
+            ItemKind::Text(item) => visitor.visit_text(item),
+            ItemKind::Image(item) => visitor.visit_image(item),
+        }
+    }
+}
+
 fn process_static<F: Fn()>(handler: F) {
     // Static dispatch - monomorphized, faster, type-safe
     handler();
 }
 
+/// A chain of `Fn(T) -> T` stages composed with static dispatch.
+///
+/// Each `then` wraps the previous chain in a new concrete closure type, so
+/// `run` is monomorphized and inlinable with no boxing. The cost is that
+/// every distinct chain is its own type, adding compile time and code size.
+pub struct Pipeline<T, F> {
+    stage: F,
+    _input: PhantomData<fn(T) -> T>,
+}
+
+impl<T, F: Fn(T) -> T> Pipeline<T, F> {
+    /// Starts a pipeline with a single stage.
+    pub fn new(stage: F) -> Self {
+        Pipeline {
+            stage,
+            _input: PhantomData,
+        }
+    }
+
+    /// Appends `next`, which runs on the output of the existing stages.
+    pub fn then<G: Fn(T) -> T>(self, next: G) -> Pipeline<T, impl Fn(T) -> T> {
+        let previous = self.stage;
+        Pipeline::new(move |input| next(previous(input)))
+    }
+
+    /// Runs every stage in order.
+    pub fn run(&self, input: T) -> T {
+        (self.stage)(input)
+    }
+}
+
+// Stages are closures, which have no Debug
+impl<T, F> std::fmt::Debug for Pipeline<T, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pipeline").finish_non_exhaustive()
+    }
+}
+
 // TRAIT_OBJECT: Required for heterogeneous collection
 fn register_handlers(handlers: Vec<Box<dyn Fn()>>) {
     // Dynamic dispatch justified and documented
@@ -102,12 +268,140 @@ fn register_handlers(handlers: Vec<Box<dyn Fn()>>) {
     }
 }
 
+/// Error returned when dispatching to a handler.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum HandlerError {
+    /// No handler is registered under this name.
+    NotFound { name: String },
+}
+
+/// Handlers registered and dispatched by name.
+#[derive(Default)]
+pub struct HandlerRegistry {
+    // TRAIT_OBJECT: Required for heterogeneous collection
+    handlers: BTreeMap<String, Box<dyn Fn()>>,
+}
+
+impl HandlerRegistry {
+    /// Registers `handler` under `name`, replacing any previous handler.
+    pub fn register(&mut self, name: &str, handler: impl Fn() + 'static) {
+        self.handlers.insert(name.to_owned(), Box::new(handler));
+    }
+
+    /// Runs the handler registered under `name`.
+    ///
+    /// # Errors
+    /// Returns `HandlerError::NotFound` if no handler has that name.
+    pub fn dispatch(&self, name: &str) -> Result<(), HandlerError> {
+        let handler = self
+            .handlers
+            .get(name)
+            .ok_or_else(|| HandlerError::NotFound {
+                name: name.to_owned(),
+            })?;
+        handler();
+        Ok(())
+    }
+
+    /// Runs every registered handler in name order.
+    pub fn dispatch_all(&self) {
+        self.handlers.values().for_each(|handler| handler());
+    }
+}
+
+// Handlers are closures, which have no Debug; list their names instead
+impl std::fmt::Debug for HandlerRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&String> = self.handlers.keys().collect();
+        f.debug_struct("HandlerRegistry")
+            .field("handlers", &names)
+            .finish()
+    }
+}
+
+use std::pin::Pin;
+
+/// A handler whose work is asynchronous.
+///
+/// Returns a boxed future instead of using `async fn` so the trait stays
+/// usable as `dyn AsyncHandler`. Any `Fn() -> impl Future` closure qualifies.
+pub trait AsyncHandler: Send + Sync {
+    /// Runs the handler.
+    fn handle(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
+}
+
+impl<F, Fut> AsyncHandler for F
+where
+    F: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    fn handle(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        Box::pin(self())
+    }
+}
+
+/// Async handlers registered and dispatched by name.
+#[derive(Default)]
+pub struct AsyncHandlerRegistry {
+    // TRAIT_OBJECT: Required for heterogeneous collection; each handler
+    // has its own future type, so no single generic parameter fits them all
+    handlers: BTreeMap<String, Box<dyn AsyncHandler>>,
+}
+
+impl AsyncHandlerRegistry {
+    /// Registers `handler` under `name`, replacing any previous handler.
+    pub fn register(&mut self, name: &str, handler: impl AsyncHandler + 'static) {
+        self.handlers.insert(name.to_owned(), Box::new(handler));
+    }
+
+    /// Runs the handler registered under `name` to completion.
+    ///
+    /// # Errors
+    /// Returns `HandlerError::NotFound` if no handler has that name.
+    pub async fn dispatch(&self, name: &str) -> Result<(), HandlerError> {
+        let handler = self
+            .handlers
+            .get(name)
+            .ok_or_else(|| HandlerError::NotFound {
+                name: name.to_owned(),
+            })?;
+        handler.handle().await;
+        Ok(())
+    }
+
+    /// Runs every registered handler in name order, one at a time.
+    pub async fn dispatch_all(&self) {
+        for handler in self.handlers.values() {
+            // EXPLICIT: loop, because each handler is awaited in turn
+            handler.handle().await;
+        }
+    }
+}
+
+// As for HandlerRegistry: list the handler names
+impl std::fmt::Debug for AsyncHandlerRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&String> = self.handlers.keys().collect();
+        f.debug_struct("AsyncHandlerRegistry")
+            .field("handlers", &names)
+            .finish()
+    }
+}
+
 // --- FIX 5: Isolated Unsafe with Safety Documentation ---
 // Grit: Unsafe code in dedicated module with clear documentation
 
 mod unsafe_ops {
     //! Unsafe operations with documented safety requirements.
 
+    /// Returns `true` if `data` starts at an address suitably aligned for `T`.
+    ///
+    /// Safe wrappers use this to choose between a direct read and a bytewise copy.
+    pub fn is_aligned_for<T>(data: &[u8]) -> bool {
+        data.as_ptr().cast::<T>().is_aligned()
+    }
+
     /// Reads a u32 from a byte slice.
     ///
     /// # Safety
@@ -119,27 +413,101 @@ mod unsafe_ops {
         debug_assert!(data.len() >= 4, "data too short");
         std::ptr::read(data.as_ptr() as *const u32)
     }
+
+    /// Writes a u32 into a byte slice in little-endian order.
+    ///
+    /// # Safety
+    ///
+    /// - `data` must be at least 4 bytes long
+    pub unsafe fn write_u32_le(data: &mut [u8], value: u32) {
+        debug_assert!(data.len() >= 4, "data too short");
+        std::ptr::copy_nonoverlapping(value.to_le_bytes().as_ptr(), data.as_mut_ptr(), 4);
+    }
+
+    /// Writes a u32 into a byte slice in big-endian order.
+    ///
+    /// # Safety
+    ///
+    /// - `data` must be at least 4 bytes long
+    pub unsafe fn write_u32_be(data: &mut [u8], value: u32) {
+        debug_assert!(data.len() >= 4, "data too short");
+        std::ptr::copy_nonoverlapping(value.to_be_bytes().as_ptr(), data.as_mut_ptr(), 4);
+    }
 }
 
+/// Reads a native-endian u32 from the start of `data`.
+///
+/// Misaligned slices fall back to a byte-by-byte copy, which is slightly
+/// slower than the direct aligned read but always sound.
 fn transmute_data_safe(data: &[u8]) -> Result<u32, &'static str> {
     if data.len() < 4 {
         return Err("data too short");
     }
-    // SAFETY: We verified length >= 4, assuming proper alignment
+    if !unsafe_ops::is_aligned_for::<u32>(data) {
+        return first_four_bytes(data).map(u32::from_ne_bytes);
+    }
+    // SAFETY: We verified length >= 4 and alignment for u32
     Ok(unsafe { unsafe_ops::read_u32(data) })
 }
 
+/// Reads a little-endian u32 from the start of `data`.
+///
+/// Same aligned fast path and misaligned fallback as `transmute_data_safe`.
+fn read_u32_le_safe(data: &[u8]) -> Result<u32, &'static str> {
+    if !unsafe_ops::is_aligned_for::<u32>(data) {
+        return first_four_bytes(data).map(u32::from_le_bytes);
+    }
+    transmute_data_safe(data).map(u32::from_le)
+}
+
+/// Reads a big-endian u32 from the start of `data`.
+///
+/// Same aligned fast path and misaligned fallback as `transmute_data_safe`.
+fn read_u32_be_safe(data: &[u8]) -> Result<u32, &'static str> {
+    if !unsafe_ops::is_aligned_for::<u32>(data) {
+        return first_four_bytes(data).map(u32::from_be_bytes);
+    }
+    transmute_data_safe(data).map(u32::from_be)
+}
+
+fn first_four_bytes(data: &[u8]) -> Result<[u8; 4], &'static str> {
+    data.get(..4)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("data too short")
+}
+
+fn write_u32_le_safe(data: &mut [u8], value: u32) -> Result<(), &'static str> {
+    if data.len() < 4 {
+        return Err("data too short"); // Nothing written on a short buffer
+    }
+    // SAFETY: We verified length >= 4
+    unsafe { unsafe_ops::write_u32_le(data, value) };
+    Ok(())
+}
+
+fn write_u32_be_safe(data: &mut [u8], value: u32) -> Result<(), &'static str> {
+    if data.len() < 4 {
+        return Err("data too short"); // Nothing written on a short buffer
+    }
+    // SAFETY: We verified length >= 4
+    unsafe { unsafe_ops::write_u32_be(data, value) };
+    Ok(())
+}
+
 // --- FIX 6: Owned Types in Async ---
 // Grit: Async functions take owned types to avoid lifetime complexity
 
+use futures::{Stream, StreamExt};
+
 /// User data structure.
-#[derive(Clone)]
+/// Equality and hashing are name-based: `name` is the only field.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct User {
     name: String,
 }
 
 /// Database handle (clone-friendly).
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct Db;
 
 impl Db {
@@ -163,10 +531,16 @@ pub async fn process_data(data: String) -> String {
     format!("processed: {}", data)
 }
 
+/// Processes a stream of payloads lazily, one item at a time.
+pub fn process_stream<S: Stream<Item = String>>(input: S) -> impl Stream<Item = String> {
+    // Same owned transform as process_data; items are pulled on demand (backpressure)
+    input.map(|data| format!("processed: {}", data))
+}
+
 // --- FIX 7: Exhaustive Matching ---
 // Grit: All variants explicitly handled
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Status {
     Active,
     Pending,
@@ -174,9 +548,24 @@ pub enum Status {
     Expired,
 }
 
-fn handle_status(status: Status) {
+impl Status {
+    /// Returns the variant name, e.g. for structured logging.
+    /// Exhaustive: a new variant fails to compile until it is named here.
+    #[must_use]
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            Status::Active => "Active",
+            Status::Pending => "Pending",
+            Status::Cancelled => "Cancelled",
+            Status::Expired => "Expired",
+        }
+    }
+}
+
+/// Reports a status, emitting a structured event with the variant name.
+pub fn handle_status(status: Status) {
     match status {
-        Status::Active => println!("Active!"),
+        Status::Active => info!(status = status.variant_name(), "status is active"),
         Status::Pending => {} // EXPLICIT: pending needs no action
         Status::Cancelled => {} // EXPLICIT: cancelled needs no action
         Status::Expired => {} // EXPLICIT: expired needs no action
@@ -186,10 +575,10 @@ fn handle_status(status: Status) {
 // Alternative: grouped handling with documentation
 fn handle_status_grouped(status: Status) {
     match status {
-        Status::Active => println!("Active!"),
+        Status::Active => info!(status = "Active", "status is active"),
         // INACTIVE_STATES: All non-active states handled identically
         Status::Pending | Status::Cancelled | Status::Expired => {
-            println!("Not active");
+            info!(status = ?status, "status is not active");
         }
     }
 }
@@ -206,11 +595,50 @@ fn handle_status_grouped(status: Status) {
 /// 2. `stream` - closes the TCP connection
 ///
 /// This order ensures buffered data is written before connection closes.
-pub struct Connection {
+///
+/// Generic over the stream only so tests can observe the drop order with
+/// stand-ins; real connections use the `TcpStream` default.
+#[derive(Debug)]
+pub struct Connection<S: Write = TcpStream> {
     /// Buffered writer - DROP FIRST: flushes before stream closes
-    writer: BufWriter<TcpStream>,
+    writer: BufWriter<S>,
     /// TCP stream - DROP SECOND: closes after flush complete
-    stream: TcpStream,
+    stream: S,
+}
+
+impl<S: Write> Connection<S> {
+    /// Writes out buffered data, leaving the connection open.
+    ///
+    /// Does not change the drop order above: dropping still flushes
+    /// `writer` before `stream` closes.
+    ///
+    /// # Errors
+    /// Returns the I/O error from writing to the stream.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl Connection {
+    /// Flushes and closes the current stream, then connects to `addr`.
+    ///
+    /// The new stream is opened before any field is replaced, so on error
+    /// the connection is left as it was. The old pair is then dropped in the
+    /// documented order: `writer` first, then `stream`.
+    ///
+    /// # Errors
+    /// Returns the I/O error from flushing, connecting, or cloning the new stream.
+    pub fn reconnect(&mut self, addr: &str) -> std::io::Result<()> {
+        self.writer.flush()?;
+        let stream = TcpStream::connect(addr)?;
+        let writer = BufWriter::new(stream.try_clone()?);
+
+        let old_writer = std::mem::replace(&mut self.writer, writer);
+        let old_stream = std::mem::replace(&mut self.stream, stream);
+        drop(old_writer); // DROP FIRST: already flushed above
+        drop(old_stream); // DROP SECOND: closes the old connection
+        Ok(())
+    }
 }
 
 // --- FIX 9: Standard Error Pattern with thiserror ---
@@ -218,19 +646,105 @@ pub struct Connection {
 
 use thiserror::Error;
 
-#[derive(Debug, Error)]
+/// Errors returned by the service layer.
+///
+/// Guaranteed `Send + Sync` (boxed sources are `dyn Error + Send + Sync`), so
+/// it works with `tokio::spawn` and converts into `anyhow::Error`.
+///
+/// `Clone`: non-Clone causes (`io::Error`, boxed sources) are held in an `Arc`.
+#[derive(Debug, Clone, Error)]
+#[non_exhaustive]
 pub enum ServiceError {
+    // Not #[from]: the Arc wrapper needs the manual From impl below
     #[error("IO error: {0}")]
-    Io(#[from] std::io::Error),
+    Io(std::sync::Arc<std::io::Error>),
 
     #[error("parse error: {0}")]
     Parse(#[from] std::num::ParseIntError),
 
+    #[error("config error: {0}")]
+    Config(#[from] toml::de::Error),
+
     #[error("not found: {id}")]
     NotFound { id: String },
 
+    // Like NotFound, but keeps the underlying cause (e.g. the failed DB lookup
+    // behind a cache miss); a field named `source` becomes Error::source().
+    #[error("not found: {id}")]
+    NotFoundDetailed {
+        id: String,
+        source: Option<std::sync::Arc<dyn std::error::Error + Send + Sync>>,
+    },
+
     #[error("validation failed: {reason}")]
     Validation { reason: String },
+
+    #[error("{ctx}: {source}")]
+    Context {
+        ctx: &'static str,
+        source: Box<ServiceError>,
+    },
+
+    #[error("upstream error: {reason}")]
+    Upstream { reason: String, retryable: bool },
+}
+
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ServiceError>();
+};
+
+impl ServiceError {
+    /// Builds a `Validation` error from any string-like reason.
+    pub fn validation(reason: impl Into<String>) -> Self {
+        ServiceError::Validation {
+            reason: reason.into(),
+        }
+    }
+
+    /// Wraps an IO error; same as the `From` conversion, but nameable at call sites.
+    pub fn io_from(e: std::io::Error) -> Self {
+        ServiceError::Io(std::sync::Arc::new(e))
+    }
+
+    /// Returns `true` if the failed operation may succeed when retried.
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ServiceError::Upstream { retryable, .. } => *retryable,
+            ServiceError::Context { source, .. } => source.is_retryable(),
+            ServiceError::Io(_)
+            | ServiceError::Parse(_)
+            | ServiceError::Config(_)
+            | ServiceError::NotFound { .. }
+            | ServiceError::NotFoundDetailed { .. }
+            | ServiceError::Validation { .. } => false,
+        }
+    }
+}
+
+impl From<std::io::Error> for ServiceError {
+    fn from(e: std::io::Error) -> Self {
+        ServiceError::io_from(e)
+    }
+}
+
+// Round-trips `Io` back to the original error (or a copy keeping its
+// `ErrorKind` if the Arc is shared); every other variant becomes `Other`.
+impl From<ServiceError> for std::io::Error {
+    fn from(e: ServiceError) -> Self {
+        match e {
+            ServiceError::Io(io) => std::sync::Arc::try_unwrap(io)
+                .unwrap_or_else(|shared| std::io::Error::new(shared.kind(), shared.to_string())),
+            other @ (ServiceError::Parse(_)
+            | ServiceError::Config(_)
+            | ServiceError::NotFound { .. }
+            | ServiceError::NotFoundDetailed { .. }
+            | ServiceError::Validation { .. }
+            | ServiceError::Context { .. }
+            | ServiceError::Upstream { .. }) => std::io::Error::other(other.to_string()),
+        }
+    }
 }
 
 // thiserror automatically implements:
@@ -239,11 +753,146 @@ pub enum ServiceError {
 // - From<T> for #[from] variants
 // No boilerplate!
 
+// Bridge the per-module UserError into ServiceError so `?` composes.
+// No `_` arm: a new UserError variant fails to compile here until mapped.
+impl From<UserError> for ServiceError {
+    fn from(err: UserError) -> Self {
+        match err {
+            UserError::NotFound => ServiceError::NotFound {
+                id: "<unknown>".to_owned(),
+            },
+            UserError::ParseError(reason) => ServiceError::Validation { reason },
+            UserError::EmptyCollection => ServiceError::Validation {
+                reason: "empty collection".to_owned(),
+            },
+        }
+    }
+}
+
+// Gateway bridge: ApiError -> ServiceError, keeping is_retryable() intact.
+// ApiError is #[non_exhaustive], but that only forces a `_` arm in other
+// crates; here the match stays exhaustive so new variants must be mapped.
+impl From<ApiError> for ServiceError {
+    fn from(err: ApiError) -> Self {
+        let retryable = err.is_retryable();
+        match err {
+            ApiError::NotFound => ServiceError::NotFound {
+                id: "<unknown>".to_owned(),
+            },
+            ApiError::Unauthorized => ServiceError::Validation {
+                reason: "unauthorized".to_owned(),
+            },
+            ApiError::RateLimited { retry_after } => ServiceError::Upstream {
+                reason: format!("rate limited, retry after {retry_after:?}"),
+                retryable,
+            },
+            ApiError::ServerError => ServiceError::Upstream {
+                reason: "server error".to_owned(),
+                retryable,
+            },
+        }
+    }
+}
+
+/// Lightweight error context for `ServiceError` results, without pulling in anyhow.
+pub trait ResultExt<T> {
+    /// Wraps the error in `ServiceError::Context`, keeping it as the source.
+    ///
+    /// # Errors
+    /// Returns `ServiceError::Context` if `self` is an error.
+    fn context(self, ctx: &'static str) -> Result<T, ServiceError>;
+}
+
+impl<T> ResultExt<T> for Result<T, ServiceError> {
+    fn context(self, ctx: &'static str) -> Result<T, ServiceError> {
+        self.map_err(|e| ServiceError::Context {
+            ctx,
+            source: Box::new(e),
+        })
+    }
+}
+
 fn example_error_usage() -> Result<(), ServiceError> {
     let _value: i32 = "42".parse()?; // Auto-converts ParseIntError
     Ok(())
 }
 
+/// Parses an integer in the given radix.
+///
+/// # Errors
+/// Returns `ServiceError::Validation` if `radix` is outside `2..=36`.
+/// Returns `ServiceError::Parse` if the input is not a valid number.
+pub fn parse_number_radix(input: &str, radix: u32) -> Result<i64, ServiceError> {
+    if !(2..=36).contains(&radix) {
+        return Err(ServiceError::Validation {
+            reason: format!("radix {radix} is outside 2..=36"),
+        });
+    }
+    Ok(i64::from_str_radix(input, radix)?) // Auto-converts ParseIntError
+}
+
+/// Parses any `FromStr` type.
+///
+/// # Errors
+/// Returns `ServiceError::Validation` naming the target type if parsing fails.
+pub fn parse_to<T>(input: &str) -> Result<T, ServiceError>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    let target = std::any::type_name::<T>();
+    input.parse().map_err(|e: T::Err| ServiceError::Validation {
+        reason: format!("cannot parse {input:?} as {target}: {e}"),
+    })
+}
+
+#[cfg(feature = "csv")]
+use grit::example::UserStore;
+
+/// CSV import for the library's `UserStore`, reporting failures as `ServiceError`.
+///
+/// An extension trait because `UserStore` lives in the grit library, which
+/// does not know this service's error type; bring it into scope to call
+/// `UserStore::from_csv_reader`.
+#[cfg(feature = "csv")]
+pub trait UserStoreCsvExt: Sized {
+    /// Builds a store from CSV with an `id,name` header row.
+    ///
+    /// Every id must pass `validate_id` and appear only once.
+    ///
+    /// # Errors
+    /// Returns `ServiceError::Validation` naming the 1-based data row for
+    /// unreadable CSV, a wrong field count, an invalid id, or a duplicate id.
+    fn from_csv_reader<R: std::io::Read>(reader: R) -> Result<Self, ServiceError>;
+}
+
+#[cfg(feature = "csv")]
+impl UserStoreCsvExt for UserStore {
+    fn from_csv_reader<R: std::io::Read>(reader: R) -> Result<Self, ServiceError> {
+        let mut store = UserStore::new();
+        let mut csv_reader = csv::Reader::from_reader(reader);
+        // EXPLICIT: loop, because each row can stop the import early
+        for (index, record) in csv_reader.records().enumerate() {
+            let row = index + 1;
+            let invalid = |reason: String| ServiceError::validation(format!("row {row}: {reason}"));
+            let record = record.map_err(|err| invalid(err.to_string()))?;
+            let fields = record.len();
+            let (Some(id), Some(name), 2) = (record.get(0), record.get(1), fields) else {
+                return Err(invalid(format!("expected 2 fields, found {fields}")));
+            };
+            grit::example::validate_id(id)
+                .map_err(|_| invalid("id must not be empty".to_owned()))?;
+            let user = grit::example::User {
+                name: name.to_owned(),
+            };
+            if store.insert(id.to_owned(), user).is_some() {
+                return Err(invalid(format!("duplicate id {id:?}")));
+            }
+        }
+        Ok(store)
+    }
+}
+
 // --- FIX 10: Iterator Chains Instead of Imperative Loops ---
 // Grit: Use functional iteration for transformations
 
@@ -293,8 +942,10 @@ fn process_item(_item: &DataItem) {}
 // --- FIX 11: Single Async Runtime (tokio) ---
 // Grit: Standardize on tokio for all async operations
 
-use tokio::{fs, time};
+use grit::concurrency::RetryPolicy;
+use std::future::Future;
 use std::time::Duration;
+use tokio::{fs, time};
 
 async fn read_config_grit() -> Result<String, std::io::Error> {
     // Grit: Use tokio utilities consistently
@@ -302,10 +953,154 @@ async fn read_config_grit() -> Result<String, std::io::Error> {
     Ok(contents)
 }
 
+/// Settings for the service, read from a TOML file.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ServiceConfig {
+    pub concurrency: usize,
+    pub retries: u32,
+}
+
+impl ServiceConfig {
+    /// Checks that the values are usable before they reach `bounded_map` or retries.
+    ///
+    /// # Errors
+    /// Returns `ServiceError::Validation` if `concurrency` is 0 or `retries` exceeds 100.
+    pub fn validate(&self) -> Result<(), ServiceError> {
+        if self.concurrency == 0 {
+            return Err(ServiceError::Validation {
+                reason: "concurrency must be at least 1".to_owned(),
+            });
+        }
+        if self.retries > 100 {
+            return Err(ServiceError::Validation {
+                reason: format!("retries must be at most 100, got {}", self.retries),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Loads the service configuration from a TOML file.
+///
+/// # Errors
+/// Returns `ServiceError::Io` if the file cannot be read.
+/// Returns `ServiceError::Config` if the contents are not a valid `ServiceConfig`.
+/// Returns `ServiceError::Validation` if the values are out of range.
+pub async fn load_service_config(path: String) -> Result<ServiceConfig, ServiceError> {
+    // Owned path (Rule 6); IO and parse failures convert via From
+    let contents = fs::read_to_string(&path).await?;
+    let config: ServiceConfig = toml::from_str(&contents)?;
+    config.validate()?;
+    Ok(config)
+}
+
 async fn delayed_operation() {
     // Grit: tokio::time, not async-std::task::sleep
     time::sleep(Duration::from_secs(1)).await;
-    println!("Operation complete");
+    info!("operation complete");
+}
+
+/// Error returned when `with_timeout` hits its deadline.
+#[derive(Debug, Clone, Error)]
+#[error("operation timed out after {elapsed:?}")]
+pub struct TimeoutError {
+    pub elapsed: Duration,
+}
+
+/// Bounds any future, e.g. `with_timeout(dur, delayed_operation())`.
+///
+/// # Errors
+/// Returns `TimeoutError` if `fut` does not complete within `dur`.
+pub async fn with_timeout<T>(
+    dur: Duration,
+    fut: impl Future<Output = T>,
+) -> Result<T, TimeoutError> {
+    // Grit: tokio::time::timeout, surfaced as our own error type instead of Elapsed
+    time::timeout(dur, fut)
+        .await
+        .map_err(|_| TimeoutError { elapsed: dur })
+}
+
+/// Runs CPU-bound or blocking work on tokio's blocking thread pool.
+///
+/// Use it for synchronous work that would stall an async worker thread for
+/// more than a few hundred microseconds, such as parsing a large document.
+/// Async I/O should stay on `tokio::fs` / `tokio::net` instead.
+///
+/// # Errors
+/// Returns `JoinError` if `f` panics.
+pub async fn offload_blocking<T: Send + 'static>(
+    f: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, tokio::task::JoinError> {
+    tokio::task::spawn_blocking(f).await
+}
+
+/// Completes on Ctrl-C (SIGINT everywhere) or SIGTERM (Unix only).
+///
+/// Services `tokio::select!` their main loop against it to shut down
+/// gracefully. Gated on the `signal` feature, which should enable tokio's
+/// `signal` feature. Manual test: run `main` with the feature, press Ctrl-C.
+#[cfg(feature = "signal")]
+pub async fn shutdown_signal() {
+    // If a handler cannot be installed, that branch never completes rather
+    // than triggering a spurious shutdown.
+    let ctrl_c = async {
+        if tokio::signal::ctrl_c().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => {}
+        () = terminate => {}
+    }
+}
+
+impl From<TimeoutError> for ServiceError {
+    fn from(err: TimeoutError) -> Self {
+        ServiceError::Upstream {
+            reason: err.to_string(),
+            retryable: true,
+        }
+    }
+}
+
+/// Loads the service configuration, retrying per `policy`.
+///
+/// Each attempt is bounded by `per_attempt`, so one hung read cannot use up
+/// the whole retry budget.
+///
+/// # Errors
+/// Returns the final attempt's error; a timed-out attempt is a retryable
+/// `ServiceError::Upstream`.
+pub async fn load_config_resilient(
+    path: String,
+    policy: RetryPolicy,
+    per_attempt: Duration,
+) -> Result<ServiceConfig, ServiceError> {
+    policy
+        .run(|| {
+            // Each attempt owns its path (Rule 6)
+            let attempt_path = path.clone();
+            async move {
+                with_timeout(per_attempt, load_service_config(attempt_path))
+                    .await
+                    .unwrap_or_else(|timeout| Err(timeout.into()))
+            }
+        })
+        .await
 }
 
 // --- FIX 12: #[non_exhaustive] on Public Enums ---
@@ -314,13 +1109,44 @@ async fn delayed_operation() {
 /// Errors returned by the API.
 ///
 /// Rule 11: `#[non_exhaustive]` allows adding variants in minor releases.
+/// Guaranteed `Send + Sync`, like the other public error types.
+#[derive(Debug, Clone)]
 #[non_exhaustive]
 pub enum ApiError {
     NotFound,
     Unauthorized,
+    RateLimited { retry_after: Duration },
+    ServerError,
     // New variants can be added without breaking downstream code
 }
 
+impl ApiError {
+    /// Returns `true` if the request may succeed when retried.
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ApiError::RateLimited { .. } | ApiError::ServerError => true,
+            ApiError::NotFound | ApiError::Unauthorized => false,
+        }
+    }
+
+    /// Returns the variant name, e.g. for structured logging.
+    #[must_use]
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            ApiError::NotFound => "NotFound",
+            ApiError::Unauthorized => "Unauthorized",
+            ApiError::RateLimited { .. } => "RateLimited",
+            ApiError::ServerError => "ServerError",
+        }
+    }
+}
+
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ApiError>();
+};
+
 // --- FIX 13: #[must_use] on Pure Functions ---
 // Grit: Pure functions annotated so discarding their result warns
 
@@ -344,51 +1170,922 @@ fn caller_correct() {
 //
 // When a type does not implement Clone and must be shared across
 // spawned tasks (which require 'static futures), wrap it in Arc.
-// Use a Semaphore to limit concurrency.
+// Use a Limiter (RAII guards over a Semaphore) to limit concurrency.
 
+use grit::concurrency::Limiter;
 use std::sync::Arc;
-use tokio::sync::Semaphore;
 use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+
+/// Where `download_files` fetches from; a trait so tests can inject failures.
+trait FileRepo: Send + Sync + 'static {
+    fn get(&self, file: &str) -> impl Future<Output = Result<String, std::io::Error>> + Send;
+}
 
 struct Repo; // Imagine this is not Clone (e.g., wraps a client + config)
 
-impl Repo {
+impl FileRepo for Repo {
     async fn get(&self, _file: &str) -> Result<String, std::io::Error> {
         Ok(String::new())
     }
 }
 
-async fn download_files(repo: Repo, files: Vec<String>) -> Vec<String> {
-    let concurrency = 4;
+/// Tuning for `download_files`.
+#[derive(Debug, Clone, Copy)]
+struct DownloadOptions {
+    /// Maximum downloads in flight; 0 is treated as 1.
+    concurrency: usize,
+    /// Cancel the remaining downloads as soon as one fails.
+    fail_fast: bool,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            fail_fast: false,
+        }
+    }
+}
+
+/// Downloads `files`, returning each file's byte length or error.
+///
+/// Files never started because `fail_fast` tripped come first, followed
+/// by the rest in completion order. Files skipped or cut short by
+/// `fail_fast` report `ErrorKind::Interrupted`.
+async fn download_files<R: FileRepo>(
+    repo: R,
+    files: Vec<String>,
+    opts: DownloadOptions,
+) -> Vec<(String, Result<usize, std::io::Error>)> {
     let repo = Arc::new(repo);
-    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let limiter = Limiter::new(opts.concurrency);
+    let cancel = CancellationToken::new();
     let mut join_set = JoinSet::new();
+    let mut names = HashMap::new(); // task id -> file, to report panicked tasks
+    let mut results = Vec::new();
 
-    for file in files {
-        let permit = Arc::clone(&semaphore)
-            .acquire_owned()
-            .await
-            .unwrap(); // OK in application code (Rule 3 is for libraries)
+    let mut files = files.into_iter();
+    for file in files.by_ref() {
+        let guard = limiter.acquire().await;
+        if cancel.is_cancelled() {
+            results.push((file, Err(cancelled())));
+            break; // EXPLICIT: fail_fast tripped, start no further downloads
+        }
 
         let task_repo = Arc::clone(&repo);
-        join_set.spawn(async move {
-            let result = task_repo.get(&file).await;
-            drop(permit);
-            (file, result)
+        let task_cancel = cancel.clone();
+        let task_file = file.clone();
+        let handle = join_set.spawn(async move {
+            // The guard is released when the task finishes
+            let _guard = guard;
+            // None means the download was cancelled before it finished
+            let result = tokio::select! {
+                () = task_cancel.cancelled() => None,
+                result = task_repo.get(&task_file) => Some(result),
+            };
+            if opts.fail_fast && matches!(result, Some(Err(_))) {
+                task_cancel.cancel();
+            }
+            result
         });
+        names.insert(handle.id(), file);
     }
-
-    let mut results = Vec::new();
-    while let Some(Ok((file, Ok(data)))) = join_set.join_next().await {
-        results.push(format!("{file}: {}", data.len()));
+    results.extend(files.map(|file| (file, Err(cancelled()))));
+
+    while let Some(joined) = join_set.join_next_with_id().await {
+        let (file, outcome) = match joined {
+            Ok((id, Some(result))) => (names.remove(&id), result.map(|data| data.len())),
+            Ok((id, None)) => (names.remove(&id), Err(cancelled())),
+            Err(err) => (names.remove(&err.id()), Err(std::io::Error::other(err))),
+        };
+        results.push((file.unwrap_or_default(), outcome));
     }
     results
 }
 
+fn cancelled() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::Interrupted, "download cancelled")
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Grit: Clear tokio runtime entry point
     let config = read_config_grit().await?;
     println!("Config: {}", config);
+
+    // A real service would tokio::select! its work against this
+    #[cfg(feature = "signal")]
+    shutdown_signal().await;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Records each event's level, `status` field and message, standing in
+    // for tracing-test's subscriber without another dev-dependency.
+    #[cfg(feature = "tracing")]
+    mod capture {
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Level, Metadata, Subscriber};
+
+        #[derive(Clone, Default)]
+        pub struct Capture {
+            pub events: Arc<Mutex<Vec<(Level, Option<String>)>>>,
+            pub messages: Arc<Mutex<Vec<String>>>,
+        }
+
+        #[derive(Default)]
+        struct Fields {
+            status: Option<String>,
+            message: Option<String>,
+        }
+
+        impl Visit for Fields {
+            fn record_str(&mut self, field: &Field, value: &str) {
+                if field.name() == "status" {
+                    self.status = Some(value.to_owned());
+                }
+            }
+
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                match field.name() {
+                    "status" => self.status = Some(format!("{value:?}")),
+                    "message" => self.message = Some(format!("{value:?}")),
+                    _ => {}
+                }
+            }
+        }
+
+        impl Subscriber for Capture {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, _: &Attributes<'_>) -> Id {
+                Id::from_u64(1)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, event: &Event<'_>) {
+                let mut fields = Fields::default();
+                event.record(&mut fields);
+                let level = *event.metadata().level();
+                self.events.lock().unwrap().push((level, fields.status));
+                self.messages.lock().unwrap().extend(fields.message);
+            }
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+    }
+
+    // --- FIX 1 ---
+
+    #[test]
+    fn parse_normalized_borrows_unchanged_segments() {
+        assert!(matches!(parse_normalized("user:1"), Cow::Borrowed("user")));
+    }
+
+    #[test]
+    fn parse_normalized_owns_changed_segments() {
+        assert!(matches!(parse_normalized("  user :1"), Cow::Owned(s) if s == "user"));
+        assert!(matches!(parse_normalized("User:1"), Cow::Owned(s) if s == "user"));
+        assert!(matches!(parse_normalized("ǅ:1"), Cow::Owned(s) if s == "ǆ"));
+    }
+
+    #[test]
+    fn parse_on_splits_at_the_first_matching_delimiter() {
+        assert_eq!(parse_on("a:b;c", &[':']), Some("a"));
+        assert_eq!(parse_on("a;b:c", &[':', ';']), Some("a"));
+        assert_eq!(parse_on("a:b", &[]), Some("a:b"));
+    }
+
+    #[test]
+    fn parse_segments_yields_every_segment() {
+        assert_eq!(parse_segments("a:b:c").collect::<Vec<_>>(), ["a", "b", "c"]);
+        assert_eq!(parse_segments("").collect::<Vec<_>>(), [""]);
+    }
+
+    // --- FIX 3 ---
+
+    fn assert_clone_matches<T: Clone + std::fmt::Debug>(value: &T) {
+        assert_eq!(format!("{:?}", value.clone()), format!("{value:?}"));
+    }
+
+    #[test]
+    fn error_types_clone() {
+        assert_clone_matches(&UserError::parse_error("bad"));
+        assert_clone_matches(&HandlerError::NotFound {
+            name: "x".to_owned(),
+        });
+        assert_clone_matches(&TimeoutError {
+            elapsed: Duration::from_secs(1),
+        });
+        assert_clone_matches(&ApiError::RateLimited {
+            retry_after: Duration::from_secs(1),
+        });
+    }
+
+    #[test]
+    fn user_error_converts_from_string_and_str() {
+        let message = |err: UserError| match err {
+            UserError::ParseError(msg) => msg,
+            UserError::NotFound | UserError::EmptyCollection => String::new(),
+        };
+        assert_eq!(message(UserError::from(String::from("bad"))), "bad");
+        assert_eq!(message(UserError::from("worse")), "worse");
+        assert_eq!(message("worst".into()), "worst");
+    }
+
+    // --- FIX 4 ---
+
+    #[test]
+    fn handler_registry_dispatches_by_name() {
+        let calls = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let mut registry = HandlerRegistry::default();
+        for name in ["save", "load"] {
+            let calls = std::rc::Rc::clone(&calls);
+            registry.register(name, move || calls.borrow_mut().push(name));
+        }
+        assert!(registry.dispatch("load").is_ok());
+        assert_eq!(*calls.borrow(), ["load"]);
+        registry.dispatch_all();
+        assert_eq!(*calls.borrow(), ["load", "load", "save"]);
+    }
+
+    #[test]
+    fn handler_registry_unknown_name_is_not_found() {
+        let registry = HandlerRegistry::default();
+        assert!(matches!(
+            registry.dispatch("missing"),
+            Err(HandlerError::NotFound { name }) if name == "missing"
+        ));
+    }
+
+    #[test]
+    fn pipeline_runs_stages_in_order() {
+        let uppercase = Pipeline::new(|s: String| s.to_uppercase());
+        let pipeline = uppercase.then(|s| s.chars().rev().collect());
+        assert_eq!(pipeline.run("grit".to_owned()), "TIRG");
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn process_all_processes_every_variant() {
+        let capture = capture::Capture::default();
+        let items = [
+            ItemKind::Text(TextItem {
+                text: "hi".to_owned(),
+            }),
+            ItemKind::Image(ImageItem { bytes: vec![0; 3] }),
+        ];
+        tracing::subscriber::with_default(capture.clone(), || process_all(&items));
+        let messages = capture.messages.lock().unwrap();
+        assert_eq!(*messages, ["processing text item", "processing image item"]);
+    }
+
+    #[derive(Default)]
+    struct CountingVisitor {
+        texts: usize,
+        images: usize,
+    }
+
+    impl Visitor for CountingVisitor {
+        fn visit_text(&mut self, _item: &TextItem) {
+            self.texts += 1;
+        }
+
+        fn visit_image(&mut self, _item: &ImageItem) {
+            self.images += 1;
+        }
+    }
+
+    #[test]
+    fn visitor_counts_each_variant() {
+        let text = || {
+            ItemKind::Text(TextItem {
+                text: String::new(),
+            })
+        };
+        let image = || ItemKind::Image(ImageItem { bytes: Vec::new() });
+        let items = [text(), image(), text()];
+        let mut visitor = CountingVisitor::default();
+        items.iter().for_each(|item| item.accept(&mut visitor));
+        assert_eq!((visitor.texts, visitor.images), (2, 1));
+    }
+
+    #[tokio::test]
+    async fn async_handler_registry_dispatches_every_handler() {
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut registry = AsyncHandlerRegistry::default();
+        for name in ["save", "load"] {
+            let calls = Arc::clone(&calls);
+            registry.register(name, move || {
+                let calls = Arc::clone(&calls);
+                async move {
+                    tokio::task::yield_now().await;
+                    calls.lock().unwrap().push(name);
+                }
+            });
+        }
+        registry.dispatch_all().await;
+        assert_eq!(*calls.lock().unwrap(), ["load", "save"]);
+    }
+
+    // --- FIX 5 ---
+
+    #[test]
+    fn write_u32_round_trips_through_read() {
+        let mut buf = [0_u8; 4];
+        write_u32_le_safe(&mut buf, 0x1234_5678).unwrap();
+        assert_eq!(buf, [0x78, 0x56, 0x34, 0x12]);
+        assert_eq!(read_u32_le_safe(&buf), Ok(0x1234_5678));
+        write_u32_be_safe(&mut buf, 0x1234_5678).unwrap();
+        assert_eq!(buf, [0x12, 0x34, 0x56, 0x78]);
+        assert_eq!(read_u32_be_safe(&buf), Ok(0x1234_5678));
+    }
+
+    #[test]
+    fn write_u32_rejects_a_short_buffer_without_writing() {
+        let mut buf = [0xAA_u8; 3];
+        assert_eq!(write_u32_le_safe(&mut buf, u32::MAX), Err("data too short"));
+        assert_eq!(write_u32_be_safe(&mut buf, u32::MAX), Err("data too short"));
+        assert_eq!(buf, [0xAA; 3]);
+    }
+
+    #[test]
+    fn reads_from_a_misaligned_subslice_match_from_bytes() {
+        #[repr(align(4))]
+        struct Aligned([u8; 8]);
+
+        let buf = Aligned([1, 2, 3, 4, 5, 6, 7, 8]);
+        assert!(unsafe_ops::is_aligned_for::<u32>(&buf.0));
+        let misaligned = &buf.0[1..5];
+        assert!(!unsafe_ops::is_aligned_for::<u32>(misaligned));
+        let bytes = [2, 3, 4, 5];
+        assert_eq!(read_u32_le_safe(misaligned), Ok(u32::from_le_bytes(bytes)));
+        assert_eq!(read_u32_be_safe(misaligned), Ok(u32::from_be_bytes(bytes)));
+        let native = u32::from_ne_bytes(bytes);
+        assert_eq!(transmute_data_safe(misaligned), Ok(native));
+    }
+
+    // --- FIX 6 ---
+
+    #[tokio::test]
+    async fn process_stream_maps_items_in_order() {
+        let input = futures::stream::iter(["a", "b", "c"].map(str::to_owned));
+        let output: Vec<String> = process_stream(input).collect().await;
+        assert_eq!(output, ["processed: a", "processed: b", "processed: c"]);
+    }
+
+    // --- FIX 7 ---
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn handle_status_active_emits_info_event() {
+        let capture = capture::Capture::default();
+        tracing::subscriber::with_default(capture.clone(), || {
+            handle_status(Status::Active);
+            handle_status(Status::Pending); // EXPLICIT: no event expected
+        });
+        let events = capture.events.lock().unwrap();
+        assert_eq!(
+            *events,
+            vec![(tracing::Level::INFO, Some("Active".to_owned()))]
+        );
+    }
+
+    #[test]
+    fn status_variant_names() {
+        let statuses = [
+            Status::Active,
+            Status::Pending,
+            Status::Cancelled,
+            Status::Expired,
+        ];
+        let names: Vec<&str> = statuses.iter().map(Status::variant_name).collect();
+        assert_eq!(names, ["Active", "Pending", "Cancelled", "Expired"]);
+    }
+
+    // --- FIX 8 ---
+
+    // A stream stand-in that pushes its name onto the shared log when dropped
+    #[derive(Debug)]
+    struct DropLog {
+        name: &'static str,
+        log: Arc<std::sync::Mutex<Vec<&'static str>>>,
+    }
+
+    impl Write for DropLog {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Drop for DropLog {
+        fn drop(&mut self) {
+            self.log.lock().unwrap().push(self.name);
+        }
+    }
+
+    #[test]
+    fn connection_fields_drop_writer_first() {
+        let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let stand_in = |name| DropLog {
+            name,
+            log: Arc::clone(&log),
+        };
+        drop(Connection {
+            writer: BufWriter::new(stand_in("writer")),
+            stream: stand_in("stream"),
+        });
+        assert_eq!(*log.lock().unwrap(), ["writer", "stream"]);
+    }
+
+    // A Connection over loopback, with the accepted server end to read from.
+    // Reads time out so a missing flush fails the test instead of hanging it.
+    fn loopback_connection() -> (Connection, TcpStream) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let writer = BufWriter::new(stream.try_clone().unwrap());
+        let (server, _) = listener.accept().unwrap();
+        let timeout = Some(Duration::from_secs(5));
+        server.set_read_timeout(timeout).unwrap();
+        (Connection { writer, stream }, server)
+    }
+
+    fn read_all(mut server: TcpStream) -> String {
+        let mut received = String::new();
+        std::io::Read::read_to_string(&mut server, &mut received).unwrap();
+        received
+    }
+
+    #[test]
+    fn connection_flush_keeps_the_connection_open() {
+        let (mut conn, mut server) = loopback_connection();
+        conn.writer.write_all(b"before ").unwrap();
+        conn.flush().unwrap();
+        let mut flushed = [0; 7];
+        std::io::Read::read_exact(&mut server, &mut flushed).unwrap();
+        assert_eq!(&flushed, b"before ");
+
+        conn.writer.write_all(b"after").unwrap();
+        drop(conn); // EXPLICIT: close, flushing the rest first
+        assert_eq!(read_all(server), "after");
+    }
+
+    #[test]
+    fn connection_reconnect_flushes_the_old_stream() {
+        let (mut conn, old_server) = loopback_connection();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+
+        conn.writer.write_all(b"old").unwrap(); // EXPLICIT: still buffered
+        conn.reconnect(&addr).unwrap();
+        let (new_server, _) = listener.accept().unwrap();
+        let timeout = Some(Duration::from_secs(5));
+        new_server.set_read_timeout(timeout).unwrap();
+        assert_eq!(read_all(old_server), "old");
+
+        conn.writer.write_all(b"new").unwrap();
+        drop(conn);
+        assert_eq!(read_all(new_server), "new");
+    }
+
+    // --- FIX 9 ---
+
+    #[test]
+    fn service_error_variants_clone() {
+        let errors = [
+            ServiceError::from(std::io::Error::new(std::io::ErrorKind::NotFound, "gone")),
+            ServiceError::from("x".parse::<i32>().unwrap_err()),
+            ServiceError::from(toml::from_str::<ServiceConfig>("x").unwrap_err()),
+            ServiceError::NotFound { id: "a".to_owned() },
+            ServiceError::NotFoundDetailed {
+                id: "a".to_owned(),
+                source: None,
+            },
+            ServiceError::validation("bad"),
+            Err::<(), _>(ServiceError::validation("bad"))
+                .context("loading")
+                .unwrap_err(),
+            ServiceError::Upstream {
+                reason: "down".to_owned(),
+                retryable: true,
+            },
+        ];
+        for err in &errors {
+            assert_eq!(err.clone().to_string(), err.to_string());
+        }
+    }
+
+    #[test]
+    fn service_error_io_clone_shares_the_error() {
+        let err = ServiceError::io_from(std::io::Error::other("disk"));
+        let (ServiceError::Io(original), ServiceError::Io(copy)) = (&err, &err.clone()) else {
+            unreachable!("io_from builds an Io variant");
+        };
+        assert!(Arc::ptr_eq(original, copy));
+    }
+
+    #[test]
+    fn parse_number_radix_reads_hex() {
+        assert_eq!(parse_number_radix("ff", 16).ok(), Some(255));
+    }
+
+    #[test]
+    fn parse_number_radix_rejects_radix_one() {
+        assert!(matches!(
+            parse_number_radix("1", 1),
+            Err(ServiceError::Validation { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_number_radix_reports_non_numeric_input() {
+        assert!(matches!(
+            parse_number_radix("zz", 10),
+            Err(ServiceError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn parse_to_reads_u64() {
+        assert_eq!(parse_to::<u64>("18446744073709551615").ok(), Some(u64::MAX));
+    }
+
+    #[test]
+    fn parse_to_error_names_the_target_type() {
+        let Err(ServiceError::Validation { reason }) = parse_to::<f32>("abc") else {
+            unreachable!("parse_to maps failures to Validation");
+        };
+        assert!(reason.contains("f32"), "{reason}");
+    }
+
+    #[test]
+    fn try_map_collect_stops_at_the_invalid_entry() {
+        let parsed = grit::util::try_map_collect(["1", "2", "x", "4"], parse_to::<u8>);
+        let Err(ServiceError::Validation { reason }) = parsed else {
+            unreachable!("\"x\" is not a u8");
+        };
+        assert!(reason.contains("\"x\""), "{reason}");
+        assert_eq!(
+            grit::util::try_map_collect(["1", "2"], parse_to::<u8>).ok(),
+            Some(vec![1, 2])
+        );
+    }
+
+    #[test]
+    fn not_found_detailed_exposes_its_source() {
+        use std::error::Error as _;
+        let cause = std::io::Error::other("cache miss");
+        let detailed = ServiceError::NotFoundDetailed {
+            id: "a".to_owned(),
+            source: Some(Arc::new(cause)),
+        };
+        let source = detailed.source().map(ToString::to_string);
+        assert_eq!(source.as_deref(), Some("cache miss"));
+        let plain = ServiceError::NotFound { id: "a".to_owned() };
+        assert!(plain.source().is_none());
+    }
+
+    #[test]
+    fn context_display_includes_the_inner_message() {
+        let err = Err::<(), _>(ServiceError::validation("bad port"))
+            .context("loading config")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "loading config: validation failed: bad port"
+        );
+        let source = std::error::Error::source(&err).map(ToString::to_string);
+        assert_eq!(source.as_deref(), Some("validation failed: bad port"));
+    }
+
+    // The From impl has no `_` arm, so a new UserError variant is a compile
+    // error there; these cases pin down the existing mappings.
+    #[test]
+    fn user_error_maps_into_service_error() {
+        assert!(matches!(
+            ServiceError::from(UserError::NotFound),
+            ServiceError::NotFound { id } if id == "<unknown>"
+        ));
+        assert!(matches!(
+            ServiceError::from(UserError::parse_error("bad")),
+            ServiceError::Validation { reason } if reason == "bad"
+        ));
+        assert!(matches!(
+            ServiceError::from(UserError::EmptyCollection),
+            ServiceError::Validation { reason } if reason == "empty collection"
+        ));
+    }
+
+    #[test]
+    fn service_error_constructors() {
+        let from_str = ServiceError::validation("bad");
+        let from_string = ServiceError::validation(String::from("bad"));
+        assert_eq!(from_str.to_string(), from_string.to_string());
+        assert!(matches!(from_str, ServiceError::Validation { reason } if reason == "bad"));
+
+        let io = ServiceError::io_from(std::io::Error::new(std::io::ErrorKind::NotFound, "gone"));
+        assert!(matches!(io, ServiceError::Io(err) if err.kind() == std::io::ErrorKind::NotFound));
+    }
+
+    #[test]
+    fn io_error_round_trips_through_service_error() {
+        let original = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        let back = std::io::Error::from(ServiceError::from(original));
+        assert_eq!(back.kind(), std::io::ErrorKind::NotFound);
+        assert_eq!(back.to_string(), "missing");
+
+        let shared = ServiceError::from(std::io::Error::from(std::io::ErrorKind::TimedOut));
+        let _keep = shared.clone();
+        let copied = std::io::Error::from(shared);
+        assert_eq!(copied.kind(), std::io::ErrorKind::TimedOut);
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn from_csv_reader_imports_every_row() {
+        let csv = "id,name\na,Ann\nb,Bo\n";
+        let store = UserStore::from_csv_reader(csv.as_bytes()).unwrap();
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.get("a").unwrap().name, "Ann");
+        assert_eq!(store.get("b").unwrap().name, "Bo");
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn from_csv_reader_reports_the_row_with_an_empty_id() {
+        let csv = "id,name\na,Ann\n,Bo\n";
+        let err = UserStore::from_csv_reader(csv.as_bytes()).unwrap_err();
+        let ServiceError::Validation { reason } = err else {
+            unreachable!("expected a validation error, got {err:?}");
+        };
+        assert!(reason.starts_with("row 2:"), "{reason}");
+    }
+
+    // --- FIX 11 ---
+
+    // Writes `contents` to a per-process temp file and returns its path
+    fn write_fixture(name: &str, contents: &str) -> String {
+        let path = std::env::temp_dir().join(format!("grit-{}-{name}", std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[tokio::test]
+    async fn load_service_config_reads_a_valid_file() {
+        let path = write_fixture("valid.toml", "concurrency = 4\nretries = 3\n");
+        let config = load_service_config(path.clone()).await;
+        std::fs::remove_file(&path).unwrap();
+        let config = config.unwrap();
+        assert_eq!((config.concurrency, config.retries), (4, 3));
+    }
+
+    #[tokio::test]
+    async fn load_service_config_missing_file_is_io() {
+        let path = std::env::temp_dir().join("grit-no-such-config.toml");
+        let result = load_service_config(path.to_string_lossy().into_owned()).await;
+        assert!(matches!(
+            result,
+            Err(ServiceError::Io(err)) if err.kind() == std::io::ErrorKind::NotFound
+        ));
+    }
+
+    fn validation_reason(config: &ServiceConfig) -> Option<String> {
+        match config.validate() {
+            Err(ServiceError::Validation { reason }) => Some(reason),
+            Ok(()) | Err(_) => None,
+        }
+    }
+
+    #[test]
+    fn service_config_rejects_zero_concurrency() {
+        let config = ServiceConfig {
+            concurrency: 0,
+            retries: 3,
+        };
+        assert_eq!(
+            validation_reason(&config).as_deref(),
+            Some("concurrency must be at least 1")
+        );
+    }
+
+    #[test]
+    fn service_config_rejects_too_many_retries() {
+        let config = ServiceConfig {
+            concurrency: 1,
+            retries: 101,
+        };
+        assert_eq!(
+            validation_reason(&config).as_deref(),
+            Some("retries must be at most 100, got 101")
+        );
+        let limit = ServiceConfig {
+            concurrency: 1,
+            retries: 100,
+        };
+        assert!(limit.validate().is_ok());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn with_timeout_returns_output_in_time() {
+        let fut = async {
+            time::sleep(Duration::from_millis(10)).await;
+            7
+        };
+        let result = with_timeout(Duration::from_secs(1), fut).await;
+        assert_eq!(result.ok(), Some(7));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn with_timeout_reports_the_deadline() {
+        let dur = Duration::from_millis(100);
+        let result = with_timeout(dur, delayed_operation()).await;
+        assert!(matches!(result, Err(TimeoutError { elapsed }) if elapsed == dur));
+    }
+
+    #[tokio::test]
+    async fn load_config_resilient_retries_until_the_file_exists() {
+        let contents = "concurrency = 2\nretries = 1\n";
+        let path = write_fixture("resilient.toml", contents);
+        std::fs::remove_file(&path).unwrap();
+        // The first attempt finds no file; it appears before the retry
+        let writer = tokio::spawn({
+            let path = path.clone();
+            async move {
+                time::sleep(Duration::from_millis(50)).await;
+                fs::write(&path, contents).await
+            }
+        });
+        let policy = RetryPolicy::builder()
+            .base_delay(Duration::from_millis(500))
+            .try_build()
+            .unwrap();
+        let started = std::time::Instant::now();
+        let config = load_config_resilient(path.clone(), policy, Duration::from_secs(5)).await;
+        writer.await.unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(config.unwrap().concurrency, 2);
+        assert!(started.elapsed() >= Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn offload_blocking_returns_the_computed_value() {
+        let sum = offload_blocking(|| (1..=100_u32).sum::<u32>()).await;
+        assert_eq!(sum.unwrap(), 5050);
+    }
+
+    // --- FIX 12 ---
+
+    #[test]
+    fn api_error_retryability() {
+        let retry_after = Duration::from_secs(30);
+        assert!(!ApiError::NotFound.is_retryable());
+        assert!(!ApiError::Unauthorized.is_retryable());
+        assert!(ApiError::RateLimited { retry_after }.is_retryable());
+        assert!(ApiError::ServerError.is_retryable());
+    }
+
+    #[test]
+    fn api_error_retry_after_round_trips() {
+        let retry_after = Duration::from_millis(1500);
+        let err = ApiError::RateLimited { retry_after };
+        assert!(matches!(
+            err,
+            ApiError::RateLimited { retry_after: got } if got == retry_after
+        ));
+    }
+
+    #[test]
+    fn api_error_maps_into_service_error() {
+        assert!(matches!(
+            ServiceError::from(ApiError::NotFound),
+            ServiceError::NotFound { .. }
+        ));
+        assert!(matches!(
+            ServiceError::from(ApiError::Unauthorized),
+            ServiceError::Validation { reason } if reason == "unauthorized"
+        ));
+        assert!(matches!(
+            ServiceError::from(ApiError::ServerError),
+            ServiceError::Upstream { .. }
+        ));
+    }
+
+    #[test]
+    fn api_error_conversion_keeps_retryability() {
+        let errors = [
+            ApiError::NotFound,
+            ApiError::Unauthorized,
+            ApiError::RateLimited {
+                retry_after: Duration::from_secs(1),
+            },
+            ApiError::ServerError,
+        ];
+        for err in errors {
+            let retryable = err.is_retryable();
+            assert_eq!(ServiceError::from(err).is_retryable(), retryable);
+        }
+    }
+
+    #[test]
+    fn api_error_variant_names() {
+        let errors = [
+            ApiError::NotFound,
+            ApiError::Unauthorized,
+            ApiError::RateLimited {
+                retry_after: Duration::from_secs(1),
+            },
+            ApiError::ServerError,
+        ];
+        let names: Vec<&str> = errors.iter().map(ApiError::variant_name).collect();
+        assert_eq!(
+            names,
+            ["NotFound", "Unauthorized", "RateLimited", "ServerError"]
+        );
+    }
+
+    // --- FIX 14 ---
+
+    // Fails on one file straight away; every other download takes a second
+    struct FailingRepo {
+        failing: &'static str,
+    }
+
+    impl FileRepo for FailingRepo {
+        async fn get(&self, file: &str) -> Result<String, std::io::Error> {
+            if file == self.failing {
+                return Err(std::io::Error::other("broken file"));
+            }
+            time::sleep(Duration::from_secs(1)).await;
+            Ok(file.to_owned())
+        }
+    }
+
+    fn files(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| (*name).to_owned()).collect()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn download_files_without_fail_fast_finishes_the_rest() {
+        let repo = FailingRepo { failing: "bad" };
+        let opts = DownloadOptions {
+            concurrency: 2,
+            fail_fast: false,
+        };
+        let results = download_files(repo, files(&["bad", "a", "bb", "ccc"]), opts).await;
+        let mut lengths: Vec<(String, Option<usize>)> = results
+            .into_iter()
+            .map(|(file, result)| (file, result.ok()))
+            .collect();
+        lengths.sort();
+        let expected = [
+            ("a", Some(1)),
+            ("bad", None),
+            ("bb", Some(2)),
+            ("ccc", Some(3)),
+        ];
+        assert_eq!(lengths, expected.map(|(file, len)| (file.to_owned(), len)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn download_files_with_fail_fast_cancels_the_rest() {
+        let repo = FailingRepo { failing: "bad" };
+        let opts = DownloadOptions {
+            concurrency: 2,
+            fail_fast: true,
+        };
+        let results = download_files(repo, files(&["a", "bad", "bb", "ccc"]), opts).await;
+        assert_eq!(results.len(), 4);
+        for (file, result) in &results {
+            let kind = result.as_ref().err().map(std::io::Error::kind);
+            let expected = if file == "bad" {
+                std::io::ErrorKind::Other
+            } else {
+                std::io::ErrorKind::Interrupted
+            };
+            assert_eq!(kind, Some(expected), "{file}");
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn download_files_reports_the_failed_file() {
+        let repo = FailingRepo { failing: "bad" };
+        let results = download_files(repo, files(&["a", "bad"]), DownloadOptions::default()).await;
+        let failed: Vec<(&str, String)> = results
+            .iter()
+            .filter_map(|(file, result)| Some((file.as_str(), result.as_ref().err()?.to_string())))
+            .collect();
+        assert_eq!(failed, [("bad", "broken file".to_owned())]);
+        assert_eq!(results.len(), 2);
+    }
+}
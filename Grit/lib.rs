@@ -1,6 +1,10 @@
 //! Grit: Strict Rust crate-level configuration
 //!
-//! Copy these deny directives to your lib.rs or main.rs
+//! Copy these deny directives (down to `YOUR CODE STARTS HERE`) to your
+//! lib.rs or main.rs
+//!
+//! Everything below that line is Grit's own helper library, which needs the
+//! dependencies and features listed in Cargo.toml.
 
 // === GRIT CRATE-LEVEL LINTS ===
 
@@ -12,6 +16,12 @@
 #![deny(clippy::expect_used)]
 #![deny(clippy::panic)]
 #![deny(clippy::indexing_slicing)]
+// Or relax them behind a feature; declare `binary-mode = []` in your
+// Cargo.toml [features] first, or `-D warnings` rejects the unknown cfg:
+// #![cfg_attr(
+//     feature = "binary-mode",
+//     allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)
+// )]
 
 // Rule 4: No type erasure
 // (enforced via code review - no lint available)
@@ -49,37 +59,463 @@
 // === ALLOWED LINTS (too noisy) ===
 #![allow(clippy::module_name_repetitions)]
 #![allow(clippy::too_many_lines)]
+#![allow(clippy::needless_lifetimes)] // Rule 1: explicit lifetimes are intentional
 
 // === YOUR CODE STARTS HERE ===
 
+// Grit's own crate attributes, not part of the copy-ready header: without
+// the default `std` feature only the `nostd_core` subset is compiled, and
+// `binary-mode` relaxes the Rule 3 panic lints
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(
+    feature = "binary-mode",
+    allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)
+)]
+
+/// `no_std` subset: id validation and panic-free byte reading.
+///
+/// Uses only `core`, returning borrowed slices and `&'static str` errors
+/// instead of `String`s. With the `std` feature these items are re-exported
+/// through `example` and `util`.
+pub mod nostd_core {
+    /// Validates a user ID, returning it unchanged on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns `"empty id"` if the ID is empty.
+    pub fn validate_id<'a>(id: &'a str) -> Result<&'a str, &'static str> {
+        if id.is_empty() {
+            return Err("empty id");
+        }
+        Ok(id)
+    }
+
+    /// Splits `data` at `mid`, or returns `None` if `mid > data.len()`.
+    ///
+    /// Sanctioned replacement for `(&data[..mid], &data[mid..])`, which
+    /// `clippy::indexing_slicing` denies.
+    #[must_use]
+    pub fn split_at_checked<'a>(data: &'a [u8], mid: usize) -> Option<(&'a [u8], &'a [u8])> {
+        data.split_at_checked(mid)
+    }
+
+    /// Reads a little-endian `u32` from the start of `data`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `"data too short"` if `data` has fewer than 4 bytes.
+    pub fn read_u32_le(data: &[u8]) -> Result<u32, &'static str> {
+        first_four_bytes(data).map(u32::from_le_bytes)
+    }
+
+    /// Reads a big-endian `u32` from the start of `data`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `"data too short"` if `data` has fewer than 4 bytes.
+    pub fn read_u32_be(data: &[u8]) -> Result<u32, &'static str> {
+        first_four_bytes(data).map(u32::from_be_bytes)
+    }
+
+    fn first_four_bytes(data: &[u8]) -> Result<[u8; 4], &'static str> {
+        data.get(..4)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or("data too short")
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use proptest::collection::vec;
+        use proptest::prelude::*;
+
+        proptest! {
+            // Stands in for a BytesReader: a sequence of length-checked
+            // reads over arbitrary input, including empty input and reads
+            // that exactly consume what is left
+            #[test]
+            fn checked_reads_never_panic(
+                data in vec(any::<u8>(), 0..64),
+                reads in vec(0_usize..12, 0..16),
+            ) {
+                let mut rest = data.as_slice();
+                for n in reads {
+                    prop_assert_eq!(read_u32_le(rest).is_ok(), rest.len() >= 4);
+                    prop_assert_eq!(read_u32_be(rest).is_ok(), rest.len() >= 4);
+                    match split_at_checked(rest, n) {
+                        Some((head, tail)) => {
+                            prop_assert_eq!(head.len(), n);
+                            prop_assert_eq!(head.len() + tail.len(), rest.len());
+                            rest = tail;
+                        }
+                        None => prop_assert!(n > rest.len()),
+                    }
+                }
+                let empty: &[u8] = &[];
+                prop_assert_eq!(split_at_checked(rest, rest.len()), Some((rest, empty)));
+            }
+        }
+    }
+}
+
+/// Checked numeric conversions, the sanctioned alternative to `as`.
+///
+/// `clippy::as_conversions` warns because `as` silently truncates or wraps.
+/// These helpers return a `NumError` instead, and need only `core`.
+///
+/// ```
+/// use grit::num::{i64_to_u32, u64_to_u32, usize_to_u16, NumError};
+///
+/// let max = u64::from(u32::MAX);
+/// assert_eq!(u64_to_u32(max), Ok(u32::MAX));
+/// assert_eq!(u64_to_u32(max + 1), Err(NumError::TooLarge));
+/// assert_eq!(usize_to_u16(65_536), Err(NumError::TooLarge));
+/// assert_eq!(i64_to_u32(-1), Err(NumError::Negative));
+/// ```
+pub mod num {
+    /// Error returned when a value does not fit the target type.
+    ///
+    /// Rule 11: `#[non_exhaustive]` allows adding variants without breaking downstream.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum NumError {
+        /// The value is above the target type's maximum.
+        TooLarge,
+        /// The value is negative and the target type is unsigned.
+        Negative,
+    }
+
+    /// Converts a `usize` to a `u32`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NumError::TooLarge` if `n > u32::MAX`.
+    pub fn usize_to_u32(n: usize) -> Result<u32, NumError> {
+        u32::try_from(n).map_err(|_| NumError::TooLarge)
+    }
+
+    /// Converts a `usize` to a `u16`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NumError::TooLarge` if `n > u16::MAX`.
+    pub fn usize_to_u16(n: usize) -> Result<u16, NumError> {
+        u16::try_from(n).map_err(|_| NumError::TooLarge)
+    }
+
+    /// Converts a `u64` to a `usize`, which is narrower on 32-bit targets.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NumError::TooLarge` if `n > usize::MAX`.
+    pub fn u64_to_usize(n: u64) -> Result<usize, NumError> {
+        usize::try_from(n).map_err(|_| NumError::TooLarge)
+    }
+
+    /// Converts a `u64` to a `u32`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NumError::TooLarge` if `n > u32::MAX`.
+    pub fn u64_to_u32(n: u64) -> Result<u32, NumError> {
+        u32::try_from(n).map_err(|_| NumError::TooLarge)
+    }
+
+    /// Converts an `i64` to a `usize`, e.g. a length read from signed input.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NumError::Negative` if `n < 0`, or `NumError::TooLarge` if
+    /// `n > usize::MAX`.
+    pub fn i64_to_usize(n: i64) -> Result<usize, NumError> {
+        usize::try_from(n).map_err(|_| signed_error(n))
+    }
+
+    /// Converts an `i64` to a `u32`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NumError::Negative` if `n < 0`, or `NumError::TooLarge` if
+    /// `n > u32::MAX`.
+    pub fn i64_to_u32(n: i64) -> Result<u32, NumError> {
+        u32::try_from(n).map_err(|_| signed_error(n))
+    }
+
+    /// Converts a `usize` to an `i64`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NumError::TooLarge` if `n > i64::MAX`.
+    pub fn usize_to_i64(n: usize) -> Result<i64, NumError> {
+        i64::try_from(n).map_err(|_| NumError::TooLarge)
+    }
+
+    fn signed_error(n: i64) -> NumError {
+        if n < 0 {
+            NumError::Negative
+        } else {
+            NumError::TooLarge
+        }
+    }
+
+    #[cfg(test)]
+    #[allow(clippy::unwrap_used)] // Rule 3 applies to library code, not its tests
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn unsigned_narrowing_accepts_the_maximum_and_rejects_overflow() {
+            let u32_max = usize::try_from(u32::MAX).unwrap();
+            assert_eq!(usize_to_u32(u32_max), Ok(u32::MAX));
+            let u16_max = usize::from(u16::MAX);
+            assert_eq!(usize_to_u16(u16_max), Ok(u16::MAX));
+            assert_eq!(usize_to_u16(u16_max + 1), Err(NumError::TooLarge));
+            assert_eq!(u64_to_u32(u64::from(u32::MAX)), Ok(u32::MAX));
+            assert_eq!(u64_to_u32(u64::from(u32::MAX) + 1), Err(NumError::TooLarge));
+        }
+
+        // On 64-bit targets every u64 fits a usize, but not every usize a
+        // u32 or an i64
+        #[cfg(target_pointer_width = "64")]
+        #[test]
+        fn pointer_width_conversions_at_the_64_bit_boundary() {
+            let u32_max = usize::try_from(u32::MAX).unwrap();
+            assert_eq!(usize_to_u32(u32_max + 1), Err(NumError::TooLarge));
+            assert_eq!(u64_to_usize(u64::MAX), Ok(usize::MAX));
+            let i64_max = usize::try_from(i64::MAX).unwrap();
+            assert_eq!(usize_to_i64(i64_max), Ok(i64::MAX));
+            assert_eq!(usize_to_i64(i64_max + 1), Err(NumError::TooLarge));
+        }
+
+        #[test]
+        fn signed_conversions_reject_negative_and_oversized_values() {
+            assert_eq!(i64_to_u32(i64::from(u32::MAX)), Ok(u32::MAX));
+            assert_eq!(i64_to_u32(i64::from(u32::MAX) + 1), Err(NumError::TooLarge));
+            assert_eq!(i64_to_u32(-1), Err(NumError::Negative));
+            assert_eq!(i64_to_usize(0), Ok(0));
+            assert_eq!(i64_to_usize(i64::MIN), Err(NumError::Negative));
+        }
+    }
+}
+
 /// Example module following Grit rules.
+#[cfg(feature = "std")]
 pub mod example {
+    use std::borrow::Borrow;
+    use std::collections::hash_map::{DefaultHasher, Entry, RandomState};
     use std::collections::HashMap;
+    use std::future::Future;
+    use std::hash::{BuildHasher, Hash, Hasher};
+    use std::ops::Deref;
+    use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+    use futures::Stream;
 
     /// Error type for user operations.
     ///
     /// Rule 11: `#[non_exhaustive]` allows adding variants without breaking downstream.
-    #[derive(Debug)]
+    ///
+    /// `UserError` is `Send + Sync` (checked at compile time below), so it can
+    /// cross `tokio::spawn` boundaries and be wrapped by `anyhow`.
+    #[derive(Debug, Clone)]
     #[non_exhaustive]
     pub enum UserError {
         /// User was not found in the database.
         NotFound,
-        /// Database connection failed.
-        DatabaseError(String),
+        /// Database connection or query failed.
+        DatabaseError {
+            /// Description of the failure.
+            message: String,
+            /// Whether retrying the operation may succeed.
+            retryable: bool,
+        },
+        /// The collection had no items.
+        EmptyCollection,
+        /// A user with this ID already exists.
+        Conflict {
+            /// The conflicting user ID.
+            id: String,
+        },
+    }
+
+    crate::assert_non_exhaustive!(crate::example::UserError {
+        NotFound,
+        DatabaseError,
+        EmptyCollection,
+        Conflict,
+    });
+
+    impl UserError {
+        /// Builds a `DatabaseError` from a message and retry classification.
+        #[must_use]
+        pub fn database_error(message: impl Into<String>, retryable: bool) -> Self {
+            UserError::DatabaseError {
+                message: message.into(),
+                retryable,
+            }
+        }
+
+        /// Returns `true` if the failed operation may succeed when retried.
+        #[must_use]
+        pub fn is_retryable(&self) -> bool {
+            match self {
+                UserError::DatabaseError { retryable, .. } => *retryable,
+                UserError::NotFound | UserError::EmptyCollection | UserError::Conflict { .. } => {
+                    false
+                }
+            }
+        }
     }
 
+    // Fails to compile if a field ever makes UserError !Send or !Sync.
+    const _: fn() = || {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<UserError>();
+    };
+
     /// User data structure.
+    ///
+    /// Equality and hashing compare `name` only; the user's ID is the map
+    /// key, not part of the value, so two users stored under different IDs
+    /// with the same name are equal.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct User {
         /// User's display name.
         pub name: String,
     }
 
+    impl User {
+        /// Returns an owned copy, detached from the map it was borrowed from.
+        ///
+        /// Allocates a new `String` for the name.
+        #[must_use]
+        pub fn to_owned_user(&self) -> User {
+            self.clone()
+        }
+    }
+
+    /// Error type for user ID validation.
+    #[derive(Debug, Clone)]
+    #[non_exhaustive]
+    pub enum IdError {
+        /// The ID was empty.
+        Empty,
+    }
+
     /// Checks whether a user ID is syntactically valid.
     ///
     /// Rule 12: `#[must_use]` — discarding the result is likely a bug.
     #[must_use]
     pub fn is_valid_id(id: &str) -> bool {
-        !id.is_empty()
+        validate_id(id).is_ok()
+    }
+
+    /// Validates a user ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IdError::Empty` if the ID is empty.
+    pub fn validate_id(id: &str) -> Result<(), IdError> {
+        crate::nostd_core::validate_id(id)
+            .map(|_| ())
+            .map_err(|_| IdError::Empty)
+    }
+
+    /// A user ID that has passed `validate_id`.
+    ///
+    /// Holding a `UserId` is proof of validity, so lookups skip re-validation.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct UserId(String);
+
+    impl UserId {
+        /// Validates `s` and wraps it as a `UserId`.
+        ///
+        /// # Errors
+        ///
+        /// Returns the `IdError` reported by `validate_id`.
+        pub fn parse(s: &str) -> Result<Self, IdError> {
+            validate_id(s)?;
+            Ok(Self(s.to_owned()))
+        }
+
+        /// Returns the ID as a string slice.
+        #[must_use]
+        pub fn as_str<'a>(&'a self) -> &'a str {
+            &self.0
+        }
+    }
+
+    /// Error returned by `Validate::validate`.
+    ///
+    /// Rule 11: `#[non_exhaustive]` so new checks can add variants.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum ValidationError {
+        /// A value that must be non-empty was empty.
+        Empty,
+    }
+
+    /// A value that can check its own invariants.
+    pub trait Validate {
+        /// Checks the value's invariants.
+        ///
+        /// # Errors
+        ///
+        /// Returns the `ValidationError` describing the first violated invariant.
+        fn validate(&self) -> Result<(), ValidationError>;
+    }
+
+    impl Validate for UserId {
+        fn validate(&self) -> Result<(), ValidationError> {
+            validate_id(self.as_str()).map_err(|err| match err {
+                IdError::Empty => ValidationError::Empty,
+            })
+        }
+    }
+
+    /// A string slice that is guaranteed non-empty.
+    ///
+    /// The only constructor is `try_new`, so functions taking a
+    /// `NonEmptyStr` need no emptiness check of their own. Derefs to the
+    /// wrapped `str`; prefer `as_str()` at call sites (Rule 2).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct NonEmptyStr<'a>(&'a str);
+
+    impl<'a> NonEmptyStr<'a> {
+        /// Wraps `s` if it is non-empty.
+        ///
+        /// # Errors
+        ///
+        /// Returns `ValidationError::Empty` if `s` is empty.
+        pub fn try_new(s: &'a str) -> Result<Self, ValidationError> {
+            let candidate = Self(s);
+            candidate.validate()?;
+            Ok(candidate)
+        }
+
+        /// Returns the wrapped slice, borrowing from the original input.
+        #[must_use]
+        pub fn as_str(self) -> &'a str {
+            self.0
+        }
+    }
+
+    impl Deref for NonEmptyStr<'_> {
+        type Target = str;
+
+        fn deref(&self) -> &str {
+            self.0
+        }
+    }
+
+    impl Validate for NonEmptyStr<'_> {
+        fn validate(&self) -> Result<(), ValidationError> {
+            if self.0.is_empty() {
+                return Err(ValidationError::Empty);
+            }
+            Ok(())
+        }
     }
 
     /// Fetches a user by ID.
@@ -88,10 +524,2418 @@ pub mod example {
     ///
     /// Returns `UserError::NotFound` if the user doesn't exist.
     /// Returns `UserError::DatabaseError` if the database query fails.
-    pub fn get_user<'a>(
-        users: &'a HashMap<String, User>,
-        id: &str,
-    ) -> Result<&'a User, UserError> {
+    pub fn get_user<'a, Q, S>(
+        users: &'a HashMap<String, User, S>,
+        id: &Q,
+    ) -> Result<&'a User, UserError>
+    where
+        String: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        S: BuildHasher,
+    {
         users.get(id).ok_or(UserError::NotFound)
     }
+
+    /// Fetches an owned copy of a user by ID.
+    ///
+    /// Clones on every hit (one `String` allocation); prefer `get_user`
+    /// when a borrow tied to the map is acceptable.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UserError::NotFound` if the user doesn't exist.
+    pub fn get_user_owned<Q, S>(users: &HashMap<String, User, S>, id: &Q) -> Result<User, UserError>
+    where
+        String: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        S: BuildHasher,
+    {
+        get_user(users, id).map(User::to_owned_user)
+    }
+
+    /// In-memory user store keyed by user ID.
+    #[derive(Debug, Clone, Default)]
+    pub struct UserStore {
+        users: HashMap<String, User, StoreHasher>,
+    }
+
+    // Serialized as a map of id -> user, in id order so output is stable.
+    #[cfg(feature = "serde")]
+    impl serde::Serialize for UserStore {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_map(self.iter_sorted())
+        }
+    }
+
+    // Keys must pass `validate_id`, so a deserialized store holds the same
+    // invariant as one populated through `UserId`.
+    #[cfg(feature = "serde")]
+    impl<'de> serde::Deserialize<'de> for UserStore {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let users = <HashMap<String, User> as serde::Deserialize>::deserialize(deserializer)?
+                .into_iter()
+                .map(|(id, user)| match validate_id(&id) {
+                    Ok(()) => Ok((id, user)),
+                    Err(_) => Err(<D::Error as serde::de::Error>::invalid_value(
+                        serde::de::Unexpected::Str(&id),
+                        &"a valid user id",
+                    )),
+                })
+                .collect::<Result<_, D::Error>>()?;
+            Ok(UserStore { users })
+        }
+    }
+
+    /// Per-store hasher: randomly keyed by default, fixed by `with_seed`.
+    #[derive(Debug, Clone)]
+    enum StoreHasher {
+        Random(RandomState),
+        Seeded(u64),
+    }
+
+    impl Default for StoreHasher {
+        fn default() -> Self {
+            StoreHasher::Random(RandomState::new())
+        }
+    }
+
+    impl BuildHasher for StoreHasher {
+        type Hasher = DefaultHasher;
+
+        fn build_hasher(&self) -> DefaultHasher {
+            match self {
+                StoreHasher::Random(state) => state.build_hasher(),
+                StoreHasher::Seeded(seed) => {
+                    let mut hasher = DefaultHasher::new();
+                    hasher.write_u64(*seed);
+                    hasher
+                }
+            }
+        }
+    }
+
+    impl UserStore {
+        /// Creates an empty store.
+        #[must_use]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Creates an empty store whose iteration order depends only on
+        /// `seed` and the inserted keys, so it is reproducible across runs.
+        ///
+        /// For snapshot tests and debugging, not security: a known seed makes
+        /// hashes predictable, which gives up `HashMap`'s `HashDoS` resistance.
+        /// The order may still change between Rust releases.
+        #[must_use]
+        pub fn with_seed(seed: u64) -> Self {
+            Self {
+                users: HashMap::with_hasher(StoreHasher::Seeded(seed)),
+            }
+        }
+
+        /// Inserts a user, returning the previous user stored under `id`, if any.
+        pub fn insert(&mut self, id: String, user: User) -> Option<User> {
+            self.users.insert(id, user)
+        }
+
+        /// Creates the user via `make` if `id` is absent, otherwise applies `update`.
+        ///
+        /// Uses a single lookup; `make` is not called when the user exists.
+        pub fn upsert(
+            &mut self,
+            id: &str,
+            make: impl FnOnce() -> User,
+            update: impl FnOnce(&mut User),
+        ) {
+            match self.users.entry(id.to_owned()) {
+                Entry::Occupied(mut entry) => update(entry.get_mut()),
+                Entry::Vacant(entry) => {
+                    entry.insert(make());
+                }
+            }
+        }
+
+        /// Gets a user by ID.
+        /// The returned reference borrows from the store.
+        ///
+        /// # Errors
+        ///
+        /// Returns `UserError::NotFound` if the user doesn't exist.
+        pub fn get<'a>(&'a self, id: &str) -> Result<&'a User, UserError> {
+            get_user(&self.users, id)
+        }
+
+        /// Gets a user by an already-validated ID.
+        ///
+        /// # Errors
+        ///
+        /// Returns `UserError::NotFound` if the user doesn't exist.
+        pub fn get_by_id<'a>(&'a self, id: &UserId) -> Result<&'a User, UserError> {
+            self.get(id.as_str())
+        }
+
+        /// Removes a user by ID and returns it.
+        ///
+        /// # Errors
+        ///
+        /// Returns `UserError::NotFound` if the user doesn't exist.
+        pub fn remove(&mut self, id: &str) -> Result<User, UserError> {
+            self.users.remove(id).ok_or(UserError::NotFound)
+        }
+
+        /// Moves every user from `other` into this store.
+        ///
+        /// `on_conflict` decides what happens when both stores hold the same ID.
+        ///
+        /// # Errors
+        ///
+        /// With `ConflictPolicy::Error`, returns `UserError::Conflict` naming
+        /// the smallest conflicting ID; nothing is merged in that case.
+        pub fn merge(
+            &mut self,
+            other: UserStore,
+            on_conflict: ConflictPolicy,
+        ) -> Result<(), UserError> {
+            match on_conflict {
+                ConflictPolicy::KeepExisting => other.users.into_iter().for_each(|(id, user)| {
+                    self.users.entry(id).or_insert(user);
+                }),
+                ConflictPolicy::Overwrite => self.users.extend(other.users),
+                ConflictPolicy::Error => {
+                    let conflict = other
+                        .users
+                        .keys()
+                        .filter(|id| self.users.contains_key(id.as_str()))
+                        .min();
+                    if let Some(id) = conflict {
+                        return Err(UserError::Conflict { id: id.clone() });
+                    }
+                    self.users.extend(other.users);
+                }
+            }
+            Ok(())
+        }
+
+        /// Keeps only the users for which `f(id, user)` returns `true`.
+        ///
+        /// Returns the number of users removed.
+        pub fn retain(&mut self, mut f: impl FnMut(&str, &User) -> bool) -> usize {
+            let before = self.users.len();
+            self.users.retain(|id, user| f(id.as_str(), user));
+            before - self.users.len()
+        }
+
+        /// Iterates over `(id, user)` pairs in lexicographic id order.
+        ///
+        /// Collects and sorts the entries up front: O(n log n) time and an
+        /// O(n) allocation per call.
+        pub fn iter_sorted<'a>(&'a self) -> impl Iterator<Item = (&'a str, &'a User)> {
+            let mut entries: Vec<(&'a str, &'a User)> = self
+                .users
+                .iter()
+                .map(|(id, user)| (id.as_str(), user))
+                .collect();
+            entries.sort_unstable_by_key(|&(id, _)| id);
+            entries.into_iter()
+        }
+
+        /// Returns the number of stored users.
+        #[must_use]
+        pub fn len(&self) -> usize {
+            self.users.len()
+        }
+
+        /// Returns `true` if the store holds no users.
+        #[must_use]
+        pub fn is_empty(&self) -> bool {
+            self.users.is_empty()
+        }
+    }
+
+    /// How `UserStore::merge` resolves an ID present in both stores.
+    ///
+    /// Rule 11: `#[non_exhaustive]` so new policies can be added.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum ConflictPolicy {
+        /// Keep the user already in the target store.
+        KeepExisting,
+        /// Replace it with the user from the merged store.
+        Overwrite,
+        /// Fail with `UserError::Conflict` and merge nothing.
+        Error,
+    }
+
+    /// Source of users that may live in memory or behind a database.
+    ///
+    /// Rule 6: lookups take an owned `id` so the future borrows nothing but `self`.
+    pub trait UserRepository {
+        /// Finds a user by ID.
+        ///
+        /// # Errors
+        ///
+        /// Returns `UserError::NotFound` if the user doesn't exist.
+        /// Returns `UserError::DatabaseError` if the backend fails.
+        fn find(&self, id: String) -> impl Future<Output = Result<User, UserError>> + Send;
+
+        /// Streams every user in the repository.
+        ///
+        /// The stream is `'static`: it owns its items rather than borrowing
+        /// the repository (Rule 6). Defaults to an empty stream for backends
+        /// that cannot enumerate their users.
+        fn stream_all(&self) -> impl Stream<Item = Result<User, UserError>> + Send + 'static {
+            futures::stream::empty()
+        }
+    }
+
+    impl UserRepository for UserStore {
+        async fn find(&self, id: String) -> Result<User, UserError> {
+            self.get(&id).cloned()
+        }
+
+        // Snapshots owned clones in ID order, so later inserts don't affect
+        // a stream that is already being consumed.
+        fn stream_all(&self) -> impl Stream<Item = Result<User, UserError>> + Send + 'static {
+            let users: Vec<Result<User, UserError>> = self
+                .iter_sorted()
+                .map(|(_, user)| Ok(user.clone()))
+                .collect();
+            futures::stream::iter(users)
+        }
+    }
+
+    /// Caching decorator for any `UserRepository`.
+    ///
+    /// Hits are served from an in-memory cache of cloned users before
+    /// delegating to the inner repository. The cache never invalidates on its
+    /// own; call `clear()` to evict everything. Clones share the same cache.
+    #[derive(Debug, Clone)]
+    pub struct CachingRepo<R> {
+        inner: R,
+        cache: Arc<Mutex<HashMap<String, User>>>,
+    }
+
+    impl<R> CachingRepo<R> {
+        /// Wraps `inner` with an empty cache.
+        #[must_use]
+        pub fn new(inner: R) -> Self {
+            Self {
+                inner,
+                cache: Arc::new(Mutex::new(HashMap::new())),
+            }
+        }
+
+        /// Evicts every cached user.
+        pub fn clear(&self) {
+            self.lock_cache().clear();
+        }
+
+        fn lock_cache(&self) -> MutexGuard<'_, HashMap<String, User>> {
+            // A poisoned cache still holds valid clones, so keep using it.
+            self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+
+    impl<R: UserRepository + Sync> UserRepository for CachingRepo<R> {
+        async fn find(&self, id: String) -> Result<User, UserError> {
+            let cached = self.lock_cache().get(&id).cloned();
+            if let Some(user) = cached {
+                return Ok(user);
+            }
+            let user = self.inner.find(id.clone()).await?;
+            self.lock_cache().insert(id, user.clone());
+            Ok(user)
+        }
+
+        fn stream_all(&self) -> impl Stream<Item = Result<User, UserError>> + Send + 'static {
+            self.inner.stream_all()
+        }
+    }
+
+    /// Fetches a user by ID from any repository.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UserError::NotFound` if the user doesn't exist.
+    pub async fn fetch_user<R: UserRepository>(repo: R, id: String) -> Result<User, UserError> {
+        // Owned types - no lifetime complexity
+        repo.find(id).await
+    }
+
+    /// Fetches many users with at most `concurrency` lookups in flight.
+    ///
+    /// Results are returned in the same order as `ids`. A lookup task that
+    /// panics is reported as a non-retryable `UserError::DatabaseError`.
+    pub async fn fetch_users<R>(
+        repo: R,
+        ids: Vec<String>,
+        concurrency: usize,
+    ) -> Vec<Result<User, UserError>>
+    where
+        R: UserRepository + Clone + Send + Sync + 'static,
+    {
+        let results = crate::concurrency::bounded_map(ids, concurrency, move |id| {
+            let repo = repo.clone();
+            async move { repo.find(id).await }
+        })
+        .await;
+        results
+            .into_iter()
+            .map(|joined| {
+                joined.unwrap_or_else(|err| Err(UserError::database_error(err.to_string(), false)))
+            })
+            .collect()
+    }
+
+    #[cfg(test)]
+    #[allow(clippy::unwrap_used)] // Rule 3 applies to library code, not its tests
+    mod tests {
+        use super::*;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        fn user(name: &str) -> User {
+            User {
+                name: name.to_owned(),
+            }
+        }
+
+        #[test]
+        fn user_error_variants_clone() {
+            let errors = [
+                UserError::NotFound,
+                UserError::database_error("timeout", true),
+                UserError::EmptyCollection,
+                UserError::Conflict { id: "a".to_owned() },
+            ];
+            for err in &errors {
+                assert_eq!(format!("{:?}", err.clone()), format!("{err:?}"));
+            }
+        }
+
+        #[test]
+        fn user_store_clone_is_independent() {
+            let mut store = UserStore::new();
+            store.insert("a".to_owned(), user("Ann"));
+            let copy = store.clone();
+            assert!(store.remove("a").is_ok());
+            assert_eq!(copy.get("a").ok(), Some(&user("Ann")));
+            assert!(format!("{copy:?}").contains("Ann"));
+        }
+
+        #[test]
+        fn user_store_insert_then_get() {
+            let mut store = UserStore::new();
+            assert!(store.insert("a".to_owned(), user("Ann")).is_none());
+            assert_eq!(store.get("a").unwrap(), &user("Ann"));
+            assert_eq!(store.insert("a".to_owned(), user("Al")), Some(user("Ann")));
+        }
+
+        #[test]
+        fn user_store_remove_missing_is_not_found() {
+            let mut store = UserStore::new();
+            assert!(matches!(store.remove("a"), Err(UserError::NotFound)));
+        }
+
+        #[test]
+        fn user_store_len_tracks_inserts_and_removes() {
+            let mut store = UserStore::new();
+            assert!(store.is_empty());
+            store.insert("a".to_owned(), user("Ann"));
+            store.insert("b".to_owned(), user("Bo"));
+            store.insert("a".to_owned(), user("Al"));
+            assert_eq!(store.len(), 2);
+            store.remove("a").unwrap();
+            assert_eq!(store.len(), 1);
+        }
+
+        #[test]
+        fn upsert_skips_make_for_existing_user() {
+            let mut store = UserStore::new();
+            store.insert("a".to_owned(), user("Ann"));
+            store.upsert(
+                "a",
+                || unreachable!("make must not run for an existing user"),
+                |existing| existing.name.push('!'),
+            );
+            assert_eq!(store.get("a").unwrap(), &user("Ann!"));
+        }
+
+        #[test]
+        fn upsert_makes_missing_user() {
+            let mut store = UserStore::new();
+            store.upsert("a", || user("Ann"), |existing| existing.name.clear());
+            assert_eq!(store.get("a").unwrap(), &user("Ann"));
+        }
+
+        #[test]
+        fn iter_sorted_yields_ids_in_lexicographic_order() {
+            let mut store = UserStore::new();
+            for id in ["c", "a", "b10", "b2"] {
+                store.insert(id.to_owned(), user(id));
+            }
+            let ids: Vec<&str> = store.iter_sorted().map(|(id, _)| id).collect();
+            assert_eq!(ids, ["a", "b10", "b2", "c"]);
+        }
+
+        #[test]
+        fn user_id_parse_rejects_invalid_input() {
+            assert!(matches!(UserId::parse(""), Err(IdError::Empty)));
+        }
+
+        #[test]
+        fn parsed_user_id_looks_up_user() {
+            let mut store = UserStore::new();
+            store.insert("a".to_owned(), user("Ann"));
+            let id = UserId::parse("a").unwrap();
+            assert_eq!(id.as_str(), "a");
+            assert_eq!(store.get_by_id(&id).unwrap(), &user("Ann"));
+        }
+
+        #[tokio::test]
+        async fn fetch_user_goes_through_the_repository() {
+            let mut store = UserStore::new();
+            store.insert("a".to_owned(), user("Ann"));
+            let found = fetch_user(store.clone(), "a".to_owned()).await;
+            assert_eq!(found.unwrap(), user("Ann"));
+            let missing = fetch_user(store, "b".to_owned()).await;
+            assert!(matches!(missing, Err(UserError::NotFound)));
+        }
+
+        // Delegates to a store, counting how often it is asked
+        #[derive(Clone, Default)]
+        struct CountingRepo {
+            store: UserStore,
+            calls: Arc<AtomicUsize>,
+        }
+
+        impl UserRepository for CountingRepo {
+            async fn find(&self, id: String) -> Result<User, UserError> {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                self.store.find(id).await
+            }
+        }
+
+        #[tokio::test]
+        async fn caching_repo_serves_repeat_lookups_from_cache() {
+            let mut inner = CountingRepo::default();
+            inner.store.insert("a".to_owned(), user("Ann"));
+            let calls = Arc::clone(&inner.calls);
+            let repo = CachingRepo::new(inner);
+
+            assert_eq!(repo.find("a".to_owned()).await.unwrap(), user("Ann"));
+            assert_eq!(repo.find("a".to_owned()).await.unwrap(), user("Ann"));
+            assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+            repo.clear();
+            assert!(repo.find("a".to_owned()).await.is_ok());
+            assert_eq!(calls.load(Ordering::SeqCst), 2);
+        }
+
+        // Even IDs resolve to a user named after them, odd ones are missing
+        #[derive(Clone)]
+        struct ParityRepo;
+
+        impl UserRepository for ParityRepo {
+            async fn find(&self, id: String) -> Result<User, UserError> {
+                match id.parse::<u32>() {
+                    Ok(n) if n % 2 == 0 => Ok(user(&id)),
+                    Ok(_) | Err(_) => Err(UserError::NotFound),
+                }
+            }
+        }
+
+        #[tokio::test]
+        async fn fetch_users_keeps_input_order() {
+            let ids: Vec<String> = (0..6).map(|n| n.to_string()).collect();
+            let results = fetch_users(ParityRepo, ids, 2).await;
+            let names: Vec<Option<String>> = results
+                .into_iter()
+                .map(|result| result.ok().map(|found| found.name))
+                .collect();
+            let expected = [Some("0"), None, Some("2"), None, Some("4"), None];
+            assert_eq!(names, expected.map(|name| name.map(str::to_owned)));
+        }
+
+        #[test]
+        fn get_user_accepts_a_custom_hasher() {
+            let mut users: HashMap<String, User, std::hash::BuildHasherDefault<DefaultHasher>> =
+                HashMap::default();
+            users.insert("a".to_owned(), user("Ann"));
+            assert_eq!(get_user(&users, "a").unwrap(), &user("Ann"));
+        }
+
+        #[test]
+        fn get_user_accepts_str_and_string_keys() {
+            let mut users = HashMap::new();
+            users.insert("a".to_owned(), user("Ann"));
+            let owned_id = "a".to_owned();
+            assert_eq!(get_user(&users, "a").unwrap(), &user("Ann"));
+            assert_eq!(get_user(&users, &owned_id).unwrap(), &user("Ann"));
+        }
+
+        #[test]
+        fn get_user_owned_is_detached_from_the_map() {
+            let mut users = HashMap::new();
+            users.insert("a".to_owned(), user("Ann"));
+            let owned = get_user_owned(&users, "a").unwrap();
+            users.get_mut("a").unwrap().name.push('!');
+            assert_eq!(owned, user("Ann"));
+        }
+
+        #[test]
+        fn equal_users_hash_equally() {
+            let hash = |user: &User| {
+                let mut hasher = DefaultHasher::new();
+                user.hash(&mut hasher);
+                hasher.finish()
+            };
+            assert_eq!(user("Ann"), user("Ann"));
+            assert_eq!(hash(&user("Ann")), hash(&user("Ann")));
+            let set: std::collections::HashSet<User> = [user("Ann"), user("Ann")].into();
+            assert_eq!(set.len(), 1);
+        }
+
+        #[tokio::test]
+        async fn stream_all_yields_every_user_in_id_order() {
+            use futures::StreamExt as _;
+            let mut store = UserStore::new();
+            store.insert("b".to_owned(), user("Bo"));
+            store.insert("a".to_owned(), user("Ann"));
+            let users: Vec<User> = store.stream_all().map(Result::unwrap).collect().await;
+            assert_eq!(users, [user("Ann"), user("Bo")]);
+            assert_eq!(ParityRepo.stream_all().count().await, 0);
+        }
+
+        #[test]
+        fn validate_reports_empty_values() {
+            assert_eq!(UserId::parse("a").unwrap().validate(), Ok(()));
+            let empty = UserId(String::new());
+            assert_eq!(empty.validate(), Err(ValidationError::Empty));
+            assert_eq!(NonEmptyStr("a").validate(), Ok(()));
+            assert_eq!(NonEmptyStr("").validate(), Err(ValidationError::Empty));
+        }
+
+        #[test]
+        fn non_empty_str_rejects_empty_and_derefs_to_input() {
+            assert_eq!(NonEmptyStr::try_new(""), Err(ValidationError::Empty));
+            let input = "Ann";
+            let name = NonEmptyStr::try_new(input).unwrap();
+            assert_eq!(&*name, input);
+            assert_eq!(name.len(), 3);
+            assert!(std::ptr::eq(name.as_str(), input));
+        }
+
+        #[test]
+        fn database_error_carries_its_retry_classification() {
+            let transient = UserError::database_error("timeout", true);
+            let permanent = UserError::database_error("bad schema", false);
+            assert!(transient.is_retryable());
+            assert!(!permanent.is_retryable());
+            assert!(matches!(
+                permanent,
+                UserError::DatabaseError { ref message, retryable: false } if message == "bad schema"
+            ));
+            assert!(!UserError::NotFound.is_retryable());
+        }
+
+        #[test]
+        fn seeded_stores_iterate_in_the_same_order() {
+            let seeded = || {
+                let mut store = UserStore::with_seed(7);
+                for id in ["d", "a", "c", "b", "e"] {
+                    store.insert(id.to_owned(), user(id));
+                }
+                store
+            };
+            let (first, second) = (seeded(), seeded());
+            let first_ids: Vec<&String> = first.users.keys().collect();
+            let second_ids: Vec<&String> = second.users.keys().collect();
+            assert_eq!(first_ids, second_ids);
+        }
+
+        #[test]
+        fn merge_resolves_overlapping_ids_per_policy() {
+            let shard = |name: &str| {
+                let mut store = UserStore::new();
+                store.insert("a".to_owned(), user(name));
+                store.insert(name.to_owned(), user(name));
+                store
+            };
+
+            let mut kept = shard("Ann");
+            let other = shard("Bo");
+            kept.merge(other, ConflictPolicy::KeepExisting).unwrap();
+            assert_eq!(kept.get("a").unwrap(), &user("Ann"));
+            assert_eq!(kept.len(), 3);
+
+            let mut replaced = shard("Ann");
+            let other = shard("Bo");
+            replaced.merge(other, ConflictPolicy::Overwrite).unwrap();
+            assert_eq!(replaced.get("a").unwrap(), &user("Bo"));
+            assert_eq!(replaced.len(), 3);
+
+            let mut rejected = shard("Ann");
+            let other = shard("Bo");
+            let err = rejected.merge(other, ConflictPolicy::Error).unwrap_err();
+            assert!(matches!(err, UserError::Conflict { ref id } if id == "a"));
+            assert!(rejected.get("Bo").is_err());
+        }
+
+        #[test]
+        fn retain_drops_users_with_empty_names() {
+            let mut store = UserStore::new();
+            store.insert("a".to_owned(), user("Ann"));
+            store.insert("b".to_owned(), user(""));
+            store.insert("c".to_owned(), user("Cy"));
+            store.insert("d".to_owned(), user(""));
+            let removed = store.retain(|_, user| !user.name.is_empty());
+            assert_eq!(removed, 2);
+            let ids: Vec<&str> = store.iter_sorted().map(|(id, _)| id).collect();
+            assert_eq!(ids, ["a", "c"]);
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn user_store_round_trips_through_json() {
+            let mut store = UserStore::new();
+            store.insert("b".to_owned(), user("Bo"));
+            store.insert("a".to_owned(), user("Ann"));
+            let json = serde_json::to_string(&store).unwrap();
+            assert_eq!(json, r#"{"a":{"name":"Ann"},"b":{"name":"Bo"}}"#);
+            let restored: UserStore = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored.get("a").unwrap(), &user("Ann"));
+            assert_eq!(restored.get("b").unwrap(), &user("Bo"));
+            assert_eq!(restored.len(), 2);
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn user_store_rejects_invalid_ids_when_deserializing() {
+            let result = serde_json::from_str::<UserStore>(r#"{"":{"name":"Ann"}}"#);
+            assert!(result.unwrap_err().to_string().contains("a valid user id"));
+        }
+
+        proptest::proptest! {
+            // ASCII, whitespace and overlong strings all shrink toward ""
+            #[test]
+            fn is_valid_id_agrees_with_validate_id(
+                id in proptest::prop_oneof!["[ -~]{0,8}", "[ \t\n]{0,4}", ".{0,300}"],
+            ) {
+                proptest::prop_assert_eq!(is_valid_id(&id), validate_id(&id).is_ok());
+            }
+        }
+    }
+}
+
+/// Iterator combinators encoding recommended Grit patterns.
+pub mod prelude {
+    /// Extension methods for any `Iterator`.
+    ///
+    /// Rule 9: named combinators for shapes that otherwise get written as loops.
+    pub trait GritIterExt: Iterator + Sized {
+        /// Finds the first item matching `pred` and transforms it with `f`.
+        ///
+        /// Stops at the first match; `f` is called at most once.
+        fn find_transform<B, P, F>(mut self, pred: P, f: F) -> Option<B>
+        where
+            P: FnMut(&Self::Item) -> bool,
+            F: FnOnce(Self::Item) -> B,
+        {
+            self.find(pred).map(f)
+        }
+
+        /// Keeps items matching `pred` and transforms them with `f`.
+        ///
+        /// Fuses the filter and map into a single `filter_map`. The result is
+        /// lazy, so the caller chooses how (and how much) to collect.
+        fn filter_transform<B, P, F>(self, mut pred: P, mut f: F) -> impl Iterator<Item = B>
+        where
+            P: FnMut(&Self::Item) -> bool,
+            F: FnMut(Self::Item) -> B,
+        {
+            self.filter_map(move |item| pred(&item).then(|| f(item)))
+        }
+    }
+
+    impl<I: Iterator> GritIterExt for I {}
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        struct Item {
+            name: &'static str,
+            valid: bool,
+        }
+
+        const ITEMS: [Item; 4] = [
+            Item {
+                name: "a",
+                valid: false,
+            },
+            Item {
+                name: "b",
+                valid: true,
+            },
+            Item {
+                name: "c",
+                valid: true,
+            },
+            Item {
+                name: "d",
+                valid: true,
+            },
+        ];
+
+        #[test]
+        fn find_transform_stops_at_the_first_match() {
+            let mut checked = 0;
+            let mut transformed = 0;
+            let found = ITEMS.iter().find_transform(
+                |item| {
+                    checked += 1;
+                    item.valid
+                },
+                |item| {
+                    transformed += 1;
+                    item.name
+                },
+            );
+            assert_eq!(found, Some("b"));
+            assert_eq!((checked, transformed), (2, 1));
+        }
+
+        #[test]
+        fn find_transform_without_a_match_is_none() {
+            let found = ITEMS
+                .iter()
+                .find_transform(|item| item.name == "z", |item| item.name);
+            assert_eq!(found, None);
+        }
+
+        #[test]
+        fn filter_transform_skips_invalid_items_lazily() {
+            let mut checked = 0;
+            let mut names = [""; 2];
+            let valid = ITEMS.iter().filter_transform(
+                |item| {
+                    checked += 1;
+                    item.valid
+                },
+                |item| item.name,
+            );
+            for (slot, name) in names.iter_mut().zip(valid.take(2)) {
+                *slot = name;
+            }
+            assert_eq!(names, ["b", "c"]);
+            // "d" is never inspected: take(2) stops pulling after "c"
+            assert_eq!(checked, 3);
+        }
+    }
+}
+
+/// Panic-free helpers for slicing and collecting.
+#[cfg(feature = "std")]
+pub mod util {
+    use crate::example::UserError;
+
+    pub use crate::nostd_core::{read_u32_be, read_u32_le, split_at_checked};
+
+    /// Returns the first `n` bytes of `data`, or `None` if it is shorter.
+    #[must_use]
+    pub fn take_prefix<'a>(data: &'a [u8], n: usize) -> Option<&'a [u8]> {
+        data.get(..n)
+    }
+
+    /// Returns up to the first `n` items of `slice`.
+    ///
+    /// `n` is clamped to the slice length, so asking for more items than
+    /// exist returns the whole slice rather than failing.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UserError::EmptyCollection` if `slice` is empty.
+    pub fn first_n<'a, T>(slice: &'a [T], n: usize) -> Result<&'a [T], UserError> {
+        if slice.is_empty() {
+            return Err(UserError::EmptyCollection);
+        }
+        Ok(slice.get(..n).unwrap_or(slice))
+    }
+
+    /// Applies `f` to every item, collecting the outputs or stopping at the first error.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error produced by `f`; later items are not visited.
+    pub fn try_map_collect<I, T, E, F>(iter: I, f: F) -> Result<Vec<T>, E>
+    where
+        I: IntoIterator,
+        F: FnMut(I::Item) -> Result<T, E>,
+    {
+        iter.into_iter().map(f).collect()
+    }
+
+    /// Splits results into successes and failures, preserving relative order.
+    #[must_use]
+    pub fn partition_results<T, E>(
+        results: impl IntoIterator<Item = Result<T, E>>,
+    ) -> (Vec<T>, Vec<E>) {
+        results
+            .into_iter()
+            .fold((Vec::new(), Vec::new()), |(mut oks, mut errs), result| {
+                match result {
+                    Ok(value) => oks.push(value),
+                    Err(err) => errs.push(err),
+                }
+                (oks, errs)
+            })
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn split_at_checked_accepts_both_ends() {
+            let data = [1, 2, 3];
+            assert_eq!(split_at_checked(&data, 3), Some((&data[..], &[][..])));
+            assert_eq!(split_at_checked(&data, 0), Some((&[][..], &data[..])));
+        }
+
+        #[test]
+        fn out_of_range_split_is_none() {
+            assert_eq!(split_at_checked(&[1, 2, 3], 4), None);
+            assert_eq!(take_prefix(&[1, 2, 3], 4), None);
+            assert_eq!(take_prefix(&[1, 2, 3], 2), Some(&[1, 2][..]));
+        }
+
+        #[test]
+        fn first_n_clamps_to_the_slice() {
+            assert_eq!(first_n(&[1, 2, 3], 5).ok(), Some(&[1, 2, 3][..]));
+            assert_eq!(first_n(&[1, 2, 3], 2).ok(), Some(&[1, 2][..]));
+        }
+
+        #[test]
+        fn first_n_zero_is_an_empty_prefix() {
+            assert_eq!(first_n(&[1, 2, 3], 0).ok(), Some(&[][..]));
+        }
+
+        #[test]
+        fn first_n_of_empty_slice_errors() {
+            assert!(matches!(
+                first_n::<u8>(&[], 0),
+                Err(UserError::EmptyCollection)
+            ));
+        }
+
+        #[test]
+        fn partition_results_keeps_relative_order() {
+            let results = [Ok(1), Err("a"), Ok(2), Err("b"), Ok(3)];
+            assert_eq!(partition_results(results), (vec![1, 2, 3], vec!["a", "b"]));
+        }
+    }
+}
+
+/// Length-prefixed framing: a 4-byte little-endian length, then the payload.
+///
+/// Built on the panic-free `nostd_core` byte helpers, so malformed input is an
+/// error rather than an out-of-bounds panic.
+#[cfg(feature = "std")]
+pub mod codec {
+    use crate::nostd_core::{read_u32_le, split_at_checked};
+    #[cfg(feature = "codec")]
+    use tokio_util::bytes::{Buf, BytesMut};
+
+    /// Number of bytes in a frame's length prefix.
+    pub const PREFIX_LEN: usize = 4;
+
+    /// Error returned when a frame cannot be encoded or decoded.
+    ///
+    /// Rule 11: `#[non_exhaustive]` allows adding variants without breaking downstream.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum CodecError {
+        /// Fewer than `PREFIX_LEN` bytes were available.
+        TruncatedPrefix,
+        /// The prefix announced more payload bytes than were available.
+        TruncatedPayload {
+            /// Payload length announced by the prefix.
+            expected: usize,
+            /// Payload bytes actually present.
+            available: usize,
+        },
+        /// The payload is longer than the limit: `u32::MAX` for the length
+        /// prefix, or `FrameCodec`'s configured maximum.
+        FrameTooLarge {
+            /// Payload length in bytes.
+            len: usize,
+        },
+        /// The underlying transport failed.
+        Io {
+            /// Kind of the I/O error.
+            kind: std::io::ErrorKind,
+            /// Its message, kept as text so `CodecError` stays `Clone` and `Eq`.
+            message: String,
+        },
+    }
+
+    impl From<std::io::Error> for CodecError {
+        fn from(err: std::io::Error) -> Self {
+            CodecError::Io {
+                kind: err.kind(),
+                message: err.to_string(),
+            }
+        }
+    }
+
+    /// Encodes `payload` as a length-prefixed frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CodecError::FrameTooLarge` if the payload exceeds `u32::MAX` bytes.
+    pub fn encode_frame(payload: &[u8]) -> Result<Vec<u8>, CodecError> {
+        let len = u32::try_from(payload.len())
+            .map_err(|_| CodecError::FrameTooLarge { len: payload.len() })?;
+        let mut frame = Vec::with_capacity(PREFIX_LEN.saturating_add(payload.len()));
+        frame.extend_from_slice(&len.to_le_bytes());
+        frame.extend_from_slice(payload);
+        Ok(frame)
+    }
+
+    /// Decodes one frame from the start of `buf`.
+    ///
+    /// Returns the payload and the bytes after the frame, both borrowed from `buf`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CodecError::TruncatedPrefix` or `CodecError::TruncatedPayload`
+    /// if `buf` ends before the frame does.
+    pub fn decode_frame<'a>(buf: &'a [u8]) -> Result<(&'a [u8], &'a [u8]), CodecError> {
+        let (prefix, rest) =
+            split_at_checked(buf, PREFIX_LEN).ok_or(CodecError::TruncatedPrefix)?;
+        let len = read_u32_le(prefix).map_err(|_| CodecError::TruncatedPrefix)?;
+        // A length that overflows usize can never be satisfied by `rest`
+        let expected = usize::try_from(len).unwrap_or(usize::MAX);
+        split_at_checked(rest, expected).ok_or(CodecError::TruncatedPayload {
+            expected,
+            available: rest.len(),
+        })
+    }
+
+    /// Reassembles frames from data that arrives in arbitrary chunks.
+    ///
+    /// Bytes are buffered across `push` calls until a whole frame, including
+    /// a length prefix split between chunks, is available.
+    #[derive(Debug, Default)]
+    pub struct FrameDecoder {
+        buf: Vec<u8>,
+    }
+
+    impl FrameDecoder {
+        /// Creates a decoder with an empty buffer.
+        #[must_use]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Appends received bytes to the internal buffer.
+        pub fn push(&mut self, data: &[u8]) {
+            self.buf.extend_from_slice(data);
+        }
+
+        /// Removes and returns the next complete frame's payload, if buffered.
+        ///
+        /// Returns `None` while the buffered bytes end mid-frame.
+        pub fn next_frame(&mut self) -> Option<Vec<u8>> {
+            let (payload, rest) = decode_frame(&self.buf).ok()?;
+            let frame = payload.to_vec();
+            let consumed = self.buf.len() - rest.len();
+            self.buf.drain(..consumed);
+            Some(frame)
+        }
+    }
+
+    /// `tokio_util` codec for the frame protocol, e.g. `Framed<TcpStream, FrameCodec>`.
+    ///
+    /// Frames longer than `max_frame_len` are rejected as soon as their
+    /// prefix arrives, before any payload is buffered.
+    #[cfg(feature = "codec")]
+    #[derive(Debug, Clone, Copy)]
+    pub struct FrameCodec {
+        max_frame_len: usize,
+    }
+
+    #[cfg(feature = "codec")]
+    impl FrameCodec {
+        /// Default `max_frame_len`: 8 MiB.
+        pub const DEFAULT_MAX_FRAME_LEN: usize = 8 * 1024 * 1024;
+
+        /// Creates a codec accepting payloads of at most `max_frame_len` bytes.
+        #[must_use]
+        pub fn new(max_frame_len: usize) -> Self {
+            Self { max_frame_len }
+        }
+    }
+
+    #[cfg(feature = "codec")]
+    impl Default for FrameCodec {
+        fn default() -> Self {
+            Self::new(Self::DEFAULT_MAX_FRAME_LEN)
+        }
+    }
+
+    #[cfg(feature = "codec")]
+    impl tokio_util::codec::Decoder for FrameCodec {
+        type Item = Vec<u8>;
+        type Error = CodecError;
+
+        fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Vec<u8>>, CodecError> {
+            match decode_frame(src) {
+                Ok((payload, _)) if payload.len() > self.max_frame_len => {
+                    Err(CodecError::FrameTooLarge { len: payload.len() })
+                }
+                Ok((payload, rest)) => {
+                    let frame = payload.to_vec();
+                    let consumed = src.len() - rest.len();
+                    src.advance(consumed);
+                    Ok(Some(frame))
+                }
+                Err(CodecError::TruncatedPayload { expected, .. })
+                    if expected > self.max_frame_len =>
+                {
+                    Err(CodecError::FrameTooLarge { len: expected })
+                }
+                Err(CodecError::TruncatedPayload {
+                    expected,
+                    available,
+                }) => {
+                    src.reserve(expected - available);
+                    Ok(None)
+                }
+                Err(CodecError::TruncatedPrefix) => Ok(None),
+                Err(err @ (CodecError::FrameTooLarge { .. } | CodecError::Io { .. })) => Err(err),
+            }
+        }
+    }
+
+    #[cfg(feature = "codec")]
+    impl tokio_util::codec::Encoder<Vec<u8>> for FrameCodec {
+        type Error = CodecError;
+
+        fn encode(&mut self, item: Vec<u8>, dst: &mut BytesMut) -> Result<(), CodecError> {
+            if item.len() > self.max_frame_len {
+                return Err(CodecError::FrameTooLarge { len: item.len() });
+            }
+            dst.extend_from_slice(&encode_frame(&item)?);
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    #[allow(clippy::unwrap_used)] // Rule 3 applies to library code, not its tests
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn encoded_frames_decode_to_the_payload() {
+            let mut wire = encode_frame(b"hello").unwrap();
+            wire.extend_from_slice(&encode_frame(b"").unwrap());
+            let (first, rest) = decode_frame(&wire).unwrap();
+            assert_eq!(first, b"hello");
+            assert_eq!(decode_frame(rest).unwrap(), (&b""[..], &b""[..]));
+        }
+
+        #[test]
+        fn decode_frame_reports_truncation() {
+            assert_eq!(decode_frame(&[5, 0, 0]), Err(CodecError::TruncatedPrefix));
+            let short = [5, 0, 0, 0, b'h', b'i'];
+            let expected = CodecError::TruncatedPayload {
+                expected: 5,
+                available: 2,
+            };
+            assert_eq!(decode_frame(&short), Err(expected));
+        }
+
+        #[test]
+        fn frame_decoder_reassembles_byte_at_a_time_input() {
+            let wire = encode_frame(b"hello").unwrap();
+            let (last, head) = wire.split_last().unwrap();
+            let mut decoder = FrameDecoder::new();
+            for byte in head {
+                decoder.push(std::slice::from_ref(byte));
+                assert_eq!(decoder.next_frame(), None);
+            }
+            decoder.push(std::slice::from_ref(last));
+            assert_eq!(decoder.next_frame(), Some(b"hello".to_vec()));
+            assert_eq!(decoder.next_frame(), None);
+        }
+
+        #[cfg(feature = "codec")]
+        #[tokio::test]
+        async fn frame_codec_round_trips_through_framed() {
+            use futures::{SinkExt as _, StreamExt as _};
+            use tokio_util::codec::Framed;
+
+            let (client, server) = tokio::io::duplex(64);
+            let mut client = Framed::new(client, FrameCodec::default());
+            let mut server = Framed::new(server, FrameCodec::new(16));
+            client.send(b"hello".to_vec()).await.unwrap();
+            client.send(Vec::new()).await.unwrap();
+            assert_eq!(server.next().await, Some(Ok(b"hello".to_vec())));
+            assert_eq!(server.next().await, Some(Ok(Vec::new())));
+
+            client.send(vec![0; 17]).await.unwrap();
+            let too_large = CodecError::FrameTooLarge { len: 17 };
+            assert_eq!(server.next().await, Some(Err(too_large)));
+        }
+    }
+}
+
+/// Bounded-concurrency helpers built on tokio.
+#[cfg(feature = "std")]
+pub mod concurrency {
+    use std::collections::hash_map::RandomState;
+    use std::collections::HashMap;
+    use std::future::Future;
+    use std::hash::{BuildHasher, Hash, Hasher};
+    use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+    use std::time::Duration;
+    use tokio::sync::{OnceCell, OwnedSemaphorePermit, Semaphore};
+    use tokio::task::{Id, JoinError, JoinSet};
+    use tokio::time::Instant;
+    use tokio_util::sync::CancellationToken;
+
+    /// Applies `f` to every input with at most `concurrency` tasks in flight.
+    ///
+    /// Results are returned in input order. A task that panics yields its
+    /// `JoinError` in place of a value. A `concurrency` of 0 is treated as 1.
+    pub async fn bounded_map<T, U, F, Fut>(
+        inputs: Vec<T>,
+        concurrency: usize,
+        f: F,
+    ) -> Vec<Result<U, JoinError>>
+    where
+        F: Fn(T) -> Fut,
+        Fut: Future<Output = U> + Send + 'static,
+        U: Send + 'static,
+    {
+        drive(inputs, concurrency, &CancellationToken::new(), f, |_, _| {}).await
+    }
+
+    /// Like `bounded_map`, but stops spawning new tasks once `cancel` fires.
+    ///
+    /// Tasks already in flight run to completion and keep their results, so
+    /// the returned vector holds the results for a prefix of `inputs`, in
+    /// order. Inputs after that prefix are never processed.
+    pub async fn bounded_map_cancellable<T, U, F, Fut>(
+        inputs: Vec<T>,
+        concurrency: usize,
+        cancel: CancellationToken,
+        f: F,
+    ) -> Vec<Result<U, JoinError>>
+    where
+        F: Fn(T) -> Fut,
+        Fut: Future<Output = U> + Send + 'static,
+        U: Send + 'static,
+    {
+        drive(inputs, concurrency, &cancel, f, |_, _| {}).await
+    }
+
+    /// Like `bounded_map`, but reports `(completed, total)` after each task finishes.
+    ///
+    /// `on_progress` is called on the driver task (the one awaiting this
+    /// function), never inside the spawned tasks, so it must not block.
+    /// The final call is `(total, total)`.
+    pub async fn bounded_map_with_progress<T, U, F, Fut, P>(
+        inputs: Vec<T>,
+        concurrency: usize,
+        on_progress: P,
+        f: F,
+    ) -> Vec<Result<U, JoinError>>
+    where
+        F: Fn(T) -> Fut,
+        Fut: Future<Output = U> + Send + 'static,
+        U: Send + 'static,
+        P: Fn(usize, usize) + Send + Sync,
+    {
+        let never_cancelled = CancellationToken::new();
+        drive(inputs, concurrency, &never_cancelled, f, on_progress).await
+    }
+
+    /// Shared fan-out loop: in-flight work is bounded by the `JoinSet` length,
+    /// so there are no permits to leak when spawning stops early.
+    async fn drive<T, U, F, Fut, P>(
+        inputs: Vec<T>,
+        concurrency: usize,
+        cancel: &CancellationToken,
+        f: F,
+        on_join: P,
+    ) -> Vec<Result<U, JoinError>>
+    where
+        F: Fn(T) -> Fut,
+        Fut: Future<Output = U> + Send + 'static,
+        U: Send + 'static,
+        P: Fn(usize, usize),
+    {
+        let limit = concurrency.max(1);
+        let total = inputs.len();
+        let mut completed = 0;
+        let mut slots: Vec<Option<Result<U, JoinError>>> =
+            std::iter::repeat_with(|| None).take(total).collect();
+        let mut positions = HashMap::with_capacity(limit);
+        let mut pending = inputs.into_iter().enumerate();
+        let mut join_set = JoinSet::new();
+
+        loop {
+            while join_set.len() < limit && !cancel.is_cancelled() {
+                let Some((idx, input)) = pending.next() else {
+                    break;
+                };
+                let handle = join_set.spawn(f(input));
+                positions.insert(handle.id(), idx);
+            }
+            let Some(joined) = join_set.join_next_with_id().await else {
+                break;
+            };
+            let (id, result) = match joined {
+                Ok((id, output)) => (id, Ok(output)),
+                Err(err) => (err.id(), Err(err)),
+            };
+            if let Some(slot) = positions.remove(&id).and_then(|idx| slots.get_mut(idx)) {
+                *slot = Some(result);
+            }
+            completed += 1;
+            on_join(completed, total);
+        }
+        slots.into_iter().flatten().collect()
+    }
+
+    /// Awaits every task in `set`, separating outputs from join errors.
+    ///
+    /// Unlike `while let Some(Ok(v)) = set.join_next().await`, a panicked or
+    /// aborted task neither stops collection nor disappears: its `JoinError`
+    /// is returned alongside the successful outputs, both in completion order.
+    pub async fn collect_join_set<T: 'static>(mut set: JoinSet<T>) -> (Vec<T>, Vec<JoinError>) {
+        let mut joined = Vec::with_capacity(set.len());
+        while let Some(result) = set.join_next().await {
+            joined.push(result);
+        }
+        crate::util::partition_results(joined)
+    }
+
+    /// A set of named tasks sharing one cancellation token.
+    ///
+    /// Formalizes the spawn-and-collect lifecycle of a `JoinSet` with a
+    /// shutdown path: tasks receive the group's token and should return once
+    /// it is cancelled.
+    #[derive(Debug, Default)]
+    pub struct TaskGroup {
+        tasks: JoinSet<()>,
+        names: HashMap<Id, String>,
+        cancel: CancellationToken,
+    }
+
+    impl TaskGroup {
+        /// Creates an empty group.
+        #[must_use]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Spawns `task`, passing it a clone of the group's cancellation token.
+        pub fn spawn<F, Fut>(&mut self, name: &str, task: F)
+        where
+            F: FnOnce(CancellationToken) -> Fut,
+            Fut: Future<Output = ()> + Send + 'static,
+        {
+            let handle = self.tasks.spawn(task(self.cancel.clone()));
+            self.names.insert(handle.id(), name.to_owned());
+        }
+
+        /// Cancels every task and waits up to `timeout` for them to finish.
+        ///
+        /// Tasks still running at the deadline are aborted. Returns their
+        /// names, sorted; an empty vector means a clean shutdown.
+        pub async fn shutdown(mut self, timeout: Duration) -> Vec<String> {
+            self.cancel.cancel();
+            let join_all = async {
+                while let Some(joined) = self.tasks.join_next_with_id().await {
+                    let id = match joined {
+                        Ok((id, ())) => id,
+                        Err(err) => err.id(),
+                    };
+                    self.names.remove(&id);
+                }
+            };
+            // `timeout` rather than `Instant::now() + timeout`, which would
+            // panic for deadlines past `Instant`'s range (e.g. `Duration::MAX`).
+            // EXPLICIT: on elapse, the stragglers are aborted just below.
+            let _ = tokio::time::timeout(timeout, join_all).await;
+            self.tasks.shutdown().await;
+            let mut unfinished: Vec<String> = self.names.into_values().collect();
+            unfinished.sort_unstable();
+            unfinished
+        }
+    }
+
+    /// Caps how many guarded sections run at once.
+    ///
+    /// A thin wrapper over `tokio::sync::Semaphore` that hands out RAII
+    /// `ConcurrencyGuard`s instead of raw permits. Clones share the same
+    /// permits, so a clone can be moved into each spawned task.
+    #[derive(Debug, Clone)]
+    pub struct Limiter {
+        semaphore: Arc<Semaphore>,
+    }
+
+    impl Limiter {
+        /// Creates a limiter allowing `permits` concurrent guards; 0 is treated as 1.
+        #[must_use]
+        pub fn new(permits: usize) -> Self {
+            Self {
+                semaphore: Arc::new(Semaphore::new(permits.max(1))),
+            }
+        }
+
+        /// Waits for a free permit and returns a guard holding it.
+        pub async fn acquire(&self) -> ConcurrencyGuard {
+            match Arc::clone(&self.semaphore).acquire_owned().await {
+                Ok(permit) => ConcurrencyGuard { _permit: permit },
+                // EXPLICIT: the semaphore is private and never closed, so this
+                // cannot happen; wait forever rather than panic.
+                Err(_) => std::future::pending().await,
+            }
+        }
+
+        /// Returns a guard if a permit is free right now, or `None` to shed load.
+        #[must_use]
+        pub fn try_acquire(&self) -> Option<ConcurrencyGuard> {
+            Arc::clone(&self.semaphore)
+                .try_acquire_owned()
+                .ok()
+                .map(|permit| ConcurrencyGuard { _permit: permit })
+        }
+    }
+
+    /// A held `Limiter` permit, released when the guard is dropped.
+    #[derive(Debug)]
+    #[must_use = "the permit is released as soon as the guard is dropped"]
+    pub struct ConcurrencyGuard {
+        _permit: OwnedSemaphorePermit,
+    }
+
+    /// Memoizes async computations by key, running each `init` at most once.
+    ///
+    /// Concurrent `get_or_init` calls for the same key share one in-flight
+    /// initialization (single-flight): each key owns a `tokio::sync::OnceCell`,
+    /// so callers for other keys are never blocked.
+    ///
+    /// The cache is unbounded and never evicts on its own; call `clear()` to
+    /// release memory.
+    #[derive(Debug)]
+    pub struct AsyncCache<K, V> {
+        cells: Mutex<HashMap<K, Arc<OnceCell<V>>>>,
+    }
+
+    impl<K, V> Default for AsyncCache<K, V> {
+        fn default() -> Self {
+            Self {
+                cells: Mutex::new(HashMap::new()),
+            }
+        }
+    }
+
+    impl<K: Eq + Hash, V: Clone> AsyncCache<K, V> {
+        /// Creates an empty cache.
+        #[must_use]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Returns the cached value for `key`, running `init` to produce it if absent.
+        ///
+        /// If another caller is already initializing `key`, waits for that
+        /// result and drops `init` without polling it.
+        pub async fn get_or_init(&self, key: K, init: impl Future<Output = V>) -> V {
+            let cell = Arc::clone(self.lock_cells().entry(key).or_default());
+            cell.get_or_init(|| init).await.clone()
+        }
+
+        /// Evicts every entry.
+        ///
+        /// Initializations already in flight still complete for their
+        /// waiters, but their results are not cached.
+        pub fn clear(&self) {
+            self.lock_cells().clear();
+        }
+
+        fn lock_cells(&self) -> MutexGuard<'_, HashMap<K, Arc<OnceCell<V>>>> {
+            // The map only holds Arcs, so a poisoned lock is still consistent.
+            self.cells.lock().unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+
+    /// Token-bucket rate limiter allowing `rate` acquisitions per `per`.
+    ///
+    /// Up to `rate` calls proceed immediately from a full bucket; after that,
+    /// calls are spaced `per / rate` apart using tokio timers.
+    ///
+    /// # Fairness
+    ///
+    /// Waiters are served in the order they called `acquire`: the state sits
+    /// behind a FIFO-fair `tokio::sync::Mutex`, and each waiter holds it while
+    /// sleeping, so later callers cannot overtake.
+    #[derive(Debug)]
+    pub struct RateLimiter {
+        interval: Duration,
+        burst: Duration,
+        // Theoretical arrival time of the next call (GCRA form of a token bucket).
+        next: tokio::sync::Mutex<Instant>,
+    }
+
+    impl RateLimiter {
+        /// Creates a limiter with a full bucket. A `rate` of 0 is treated as 1.
+        #[must_use]
+        pub fn new(rate: u32, per: Duration) -> Self {
+            let interval = per / rate.max(1);
+            Self {
+                interval,
+                burst: per.saturating_sub(interval),
+                next: tokio::sync::Mutex::new(Instant::now()),
+            }
+        }
+
+        /// Waits until a call is allowed under the rate limit.
+        pub async fn acquire(&self) {
+            let mut next = self.next.lock().await;
+            let now = Instant::now();
+            let wait = next
+                .saturating_duration_since(now)
+                .saturating_sub(self.burst);
+            if !wait.is_zero() {
+                tokio::time::sleep(wait).await;
+            }
+            *next = (*next).max(now) + self.interval;
+        }
+    }
+
+    /// Error returned when a `RetryPolicy` is misconfigured.
+    #[derive(Debug, Clone)]
+    #[non_exhaustive]
+    pub enum RetryPolicyError {
+        /// `max_attempts` was 0, so the operation would never run.
+        ZeroAttempts,
+    }
+
+    impl std::fmt::Display for RetryPolicyError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                RetryPolicyError::ZeroAttempts => f.write_str("max_attempts must be at least 1"),
+            }
+        }
+    }
+
+    impl std::error::Error for RetryPolicyError {}
+
+    /// Reusable retry configuration with capped exponential backoff.
+    ///
+    /// Built via `RetryPolicy::builder()` so each setting is named at the call site.
+    #[derive(Debug, Clone)]
+    pub struct RetryPolicy {
+        max_attempts: u32,
+        base_delay: Duration,
+        max_delay: Duration,
+        jitter: bool,
+    }
+
+    impl RetryPolicy {
+        /// Starts a builder with 3 attempts, 100ms base delay, 10s cap and no jitter.
+        #[must_use]
+        pub fn builder() -> RetryPolicyBuilder {
+            RetryPolicyBuilder {
+                max_attempts: 3,
+                base_delay: Duration::from_millis(100),
+                max_delay: Duration::from_secs(10),
+                jitter: false,
+            }
+        }
+
+        /// Returns the un-jittered delay after the `attempt`-th failure (1-based).
+        ///
+        /// Doubles from `base_delay` on each attempt and never exceeds `max_delay`.
+        #[must_use]
+        pub fn delay_for(&self, attempt: u32) -> Duration {
+            let factor = 2_u32
+                .checked_pow(attempt.saturating_sub(1))
+                .unwrap_or(u32::MAX);
+            self.base_delay.saturating_mul(factor).min(self.max_delay)
+        }
+
+        /// Runs `op` until it succeeds or `max_attempts` is reached.
+        ///
+        /// Sleeps between attempts with `tokio::time::sleep`.
+        ///
+        /// # Errors
+        ///
+        /// Returns the error from the final attempt if every attempt fails.
+        pub async fn run<T, E, F, Fut>(&self, mut op: F) -> Result<T, E>
+        where
+            F: FnMut() -> Fut,
+            Fut: Future<Output = Result<T, E>>,
+        {
+            let mut attempt = 1;
+            loop {
+                match op().await {
+                    Ok(value) => return Ok(value),
+                    Err(err) if attempt >= self.max_attempts => return Err(err),
+                    Err(_) => {} // EXPLICIT: retry after the backoff below
+                }
+                tokio::time::sleep(self.backoff(attempt)).await;
+                attempt += 1;
+            }
+        }
+
+        fn backoff(&self, attempt: u32) -> Duration {
+            let delay = self.delay_for(attempt);
+            if !self.jitter {
+                return delay;
+            }
+            // Full jitter: uniform in [0, delay], seeded from the std random hasher.
+            let nanos = u64::try_from(delay.as_nanos()).unwrap_or(u64::MAX);
+            let random = RandomState::new().build_hasher().finish();
+            Duration::from_nanos(random % nanos.saturating_add(1))
+        }
+    }
+
+    /// Builder for `RetryPolicy`.
+    #[derive(Debug, Clone)]
+    pub struct RetryPolicyBuilder {
+        max_attempts: u32,
+        base_delay: Duration,
+        max_delay: Duration,
+        jitter: bool,
+    }
+
+    impl RetryPolicyBuilder {
+        /// Sets the total number of attempts, including the first.
+        #[must_use]
+        pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+            self.max_attempts = max_attempts;
+            self
+        }
+
+        /// Sets the delay after the first failure.
+        #[must_use]
+        pub fn base_delay(mut self, base_delay: Duration) -> Self {
+            self.base_delay = base_delay;
+            self
+        }
+
+        /// Sets the upper bound on any single delay.
+        #[must_use]
+        pub fn max_delay(mut self, max_delay: Duration) -> Self {
+            self.max_delay = max_delay;
+            self
+        }
+
+        /// Enables full jitter on each delay.
+        #[must_use]
+        pub fn jitter(mut self, jitter: bool) -> Self {
+            self.jitter = jitter;
+            self
+        }
+
+        /// Validates the settings and builds the policy.
+        ///
+        /// # Errors
+        ///
+        /// Returns `RetryPolicyError::ZeroAttempts` if `max_attempts` is 0.
+        pub fn try_build(self) -> Result<RetryPolicy, RetryPolicyError> {
+            if self.max_attempts == 0 {
+                return Err(RetryPolicyError::ZeroAttempts);
+            }
+            Ok(RetryPolicy {
+                max_attempts: self.max_attempts,
+                base_delay: self.base_delay,
+                max_delay: self.max_delay,
+                jitter: self.jitter,
+            })
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[tokio::test]
+        async fn task_group_shutdown_completes_cooperative_tasks() {
+            let mut group = TaskGroup::new();
+            let finished = Arc::new(AtomicUsize::new(0));
+            for name in ["a", "b"] {
+                let finished = Arc::clone(&finished);
+                group.spawn(name, move |cancel| async move {
+                    cancel.cancelled().await;
+                    finished.fetch_add(1, Ordering::SeqCst);
+                });
+            }
+            assert!(group.shutdown(Duration::from_secs(1)).await.is_empty());
+            assert_eq!(finished.load(Ordering::SeqCst), 2);
+        }
+
+        #[tokio::test(start_paused = true)]
+        async fn task_group_shutdown_reports_tasks_past_the_deadline() {
+            let mut group = TaskGroup::new();
+            group.spawn("stubborn", |_cancel| std::future::pending());
+            group.spawn("polite", |cancel| async move { cancel.cancelled().await });
+            let unfinished = group.shutdown(Duration::from_millis(50)).await;
+            assert_eq!(unfinished, ["stubborn"]);
+        }
+
+        #[tokio::test]
+        async fn task_group_shutdown_accepts_unbounded_timeout() {
+            let mut group = TaskGroup::new();
+            group.spawn("polite", |cancel| async move { cancel.cancelled().await });
+            assert!(group.shutdown(Duration::MAX).await.is_empty());
+        }
+
+        #[test]
+        fn retry_policy_delay_doubles_up_to_the_cap() {
+            let policy = RetryPolicy::builder()
+                .base_delay(Duration::from_millis(100))
+                .max_delay(Duration::from_secs(1))
+                .try_build();
+            let Ok(policy) = policy else {
+                unreachable!("3 attempts is a valid policy");
+            };
+            assert_eq!(policy.delay_for(1), Duration::from_millis(100));
+            assert_eq!(policy.delay_for(4), Duration::from_millis(800));
+            assert_eq!(policy.delay_for(5), Duration::from_secs(1));
+            assert_eq!(policy.delay_for(u32::MAX), Duration::from_secs(1));
+        }
+
+        #[test]
+        fn retry_policy_rejects_zero_attempts() {
+            let policy = RetryPolicy::builder().max_attempts(0).try_build();
+            assert!(matches!(policy, Err(RetryPolicyError::ZeroAttempts)));
+        }
+
+        #[test]
+        fn retry_policy_error_is_a_displayable_error() {
+            let build = || -> Result<RetryPolicy, Box<dyn std::error::Error>> {
+                Ok(RetryPolicy::builder().max_attempts(0).try_build()?)
+            };
+            let Err(err) = build() else {
+                unreachable!("0 attempts is rejected");
+            };
+            assert_eq!(err.to_string(), "max_attempts must be at least 1");
+        }
+
+        #[tokio::test]
+        async fn bounded_map_cancellable_stops_after_cancel() {
+            let cancel = CancellationToken::new();
+            let processed = Arc::new(AtomicUsize::new(0));
+            let task_cancel = cancel.clone();
+            let task_processed = Arc::clone(&processed);
+            let results = bounded_map_cancellable(vec![1, 2, 3, 4], 1, cancel, move |n| {
+                let cancel = task_cancel.clone();
+                let processed = Arc::clone(&task_processed);
+                async move {
+                    processed.fetch_add(1, Ordering::SeqCst);
+                    cancel.cancel(); // EXPLICIT: cancel once the first result is in
+                    n * 10
+                }
+            })
+            .await;
+            let values: Vec<i32> = results.into_iter().filter_map(Result::ok).collect();
+            assert_eq!(values, [10]);
+            assert_eq!(processed.load(Ordering::SeqCst), 1);
+        }
+
+        #[tokio::test]
+        async fn bounded_map_with_progress_ends_at_total() {
+            let calls = Mutex::new(Vec::new());
+            let record = |completed, total| {
+                let mut calls = calls.lock().unwrap_or_else(PoisonError::into_inner);
+                calls.push((completed, total));
+            };
+            let results =
+                bounded_map_with_progress(vec![1, 2, 3], 2, record, |n| async move { n }).await;
+            assert_eq!(results.len(), 3);
+            let calls = calls.into_inner().unwrap_or_else(PoisonError::into_inner);
+            assert_eq!(calls, [(1, 3), (2, 3), (3, 3)]);
+        }
+
+        #[tokio::test(start_paused = true)]
+        async fn rate_limiter_paces_calls_after_the_burst() {
+            let limiter = RateLimiter::new(2, Duration::from_secs(1));
+            let start = Instant::now();
+            for _ in 0..2 {
+                limiter.acquire().await;
+            }
+            assert_eq!(start.elapsed(), Duration::ZERO);
+            for _ in 0..4 {
+                limiter.acquire().await;
+            }
+            assert!(start.elapsed() >= Duration::from_secs(2));
+        }
+
+        #[tokio::test]
+        async fn async_cache_runs_init_once_for_concurrent_callers() {
+            let cache = AsyncCache::new();
+            let inits = AtomicUsize::new(0);
+            let init = || async {
+                inits.fetch_add(1, Ordering::SeqCst);
+                tokio::task::yield_now().await; // EXPLICIT: let the other caller arrive
+                42
+            };
+            let (first, second) = tokio::join!(
+                cache.get_or_init("k", init()),
+                cache.get_or_init("k", init())
+            );
+            assert_eq!((first, second), (42, 42));
+            assert_eq!(inits.load(Ordering::SeqCst), 1);
+            assert_eq!(cache.get_or_init("k", init()).await, 42);
+            assert_eq!(inits.load(Ordering::SeqCst), 1);
+        }
+
+        #[tokio::test]
+        async fn limiter_caps_concurrent_guards() {
+            let limiter = Limiter::new(2);
+            let in_flight = AtomicUsize::new(0);
+            let peak = AtomicUsize::new(0);
+            let work = (0..5).map(|_| async {
+                let _guard = limiter.acquire().await;
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::task::yield_now().await; // EXPLICIT: let the others contend
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+            });
+            futures::future::join_all(work).await;
+            assert_eq!(peak.load(Ordering::SeqCst), 2);
+        }
+
+        #[tokio::test]
+        async fn limiter_try_acquire_sheds_load_when_exhausted() {
+            let limiter = Limiter::new(2);
+            let first = limiter.acquire().await;
+            let _second = limiter.acquire().await;
+            assert!(limiter.try_acquire().is_none());
+            drop(first);
+            assert!(limiter.try_acquire().is_some());
+        }
+
+        #[tokio::test]
+        #[allow(clippy::panic)] // The task must panic to produce a JoinError
+        async fn collect_join_set_separates_panics_from_values() {
+            let mut set = JoinSet::new();
+            set.spawn(async { 7 });
+            set.spawn(async { panic!("task failed") });
+            let (values, errors) = collect_join_set(set).await;
+            assert_eq!(values, [7]);
+            assert!(errors.len() == 1 && errors.iter().all(JoinError::is_panic));
+        }
+    }
+}
+
+/// The Grit lint configuration as data, for tooling and CI.
+///
+/// Mirrors the crate-level attributes at the top of this file.
+pub mod lints {
+    /// A lint level, as in `#![deny(...)]`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Level {
+        /// `allow` / `-A`
+        Allow,
+        /// `warn` / `-W`
+        Warn,
+        /// `deny` / `-D`
+        Deny,
+        /// `forbid` / `-F`
+        Forbid,
+    }
+
+    impl Level {
+        /// Returns the rustc command-line flag for this level, e.g. `"-D"`.
+        #[must_use]
+        pub const fn flag(self) -> &'static str {
+            match self {
+                Level::Allow => "-A",
+                Level::Warn => "-W",
+                Level::Deny => "-D",
+                Level::Forbid => "-F",
+            }
+        }
+
+        /// Returns the attribute name for this level, e.g. `"deny"`.
+        #[must_use]
+        pub const fn attribute(self) -> &'static str {
+            match self {
+                Level::Allow => "allow",
+                Level::Warn => "warn",
+                Level::Deny => "deny",
+                Level::Forbid => "forbid",
+            }
+        }
+    }
+
+    /// The kind of crate the lints are applied to.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum CrateMode {
+        /// Full Grit strictness.
+        Library,
+        /// As `Library`, but the Rule 3 panic lints are allowed (the
+        /// `binary-mode` feature).
+        Binary,
+    }
+
+    /// A lint and the level Grit sets it to.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Lint {
+        /// Lint name as written in attributes, e.g. `"clippy::unwrap_used"`.
+        pub name: &'static str,
+        /// Level in `CrateMode::Library`.
+        pub level: Level,
+    }
+
+    impl Lint {
+        /// Returns the level this lint has in `mode`.
+        #[must_use]
+        pub fn level_for(self, mode: CrateMode) -> Level {
+            match mode {
+                CrateMode::Binary if BINARY_MODE_ALLOWED.contains(&self.name) => Level::Allow,
+                CrateMode::Binary | CrateMode::Library => self.level,
+            }
+        }
+    }
+
+    // Rule 3 lints relaxed by the `binary-mode` feature.
+    const BINARY_MODE_ALLOWED: &[&str] = &[
+        "clippy::unwrap_used",
+        "clippy::expect_used",
+        "clippy::panic",
+    ];
+
+    const fn lint(name: &'static str, level: Level) -> Lint {
+        Lint { name, level }
+    }
+
+    /// Every lint Grit configures, in the order of the crate-level attributes.
+    ///
+    /// Order matters: group lints such as `clippy::pedantic` come before the
+    /// individual lints that override them.
+    pub const GRIT_LINTS: &[Lint] = &[
+        // Rule 1: Explicit lifetimes on public APIs
+        lint("elided_lifetimes_in_paths", Level::Deny),
+        // Rule 3: No panics in library code
+        lint("clippy::unwrap_used", Level::Deny),
+        lint("clippy::expect_used", Level::Deny),
+        lint("clippy::panic", Level::Deny),
+        lint("clippy::indexing_slicing", Level::Deny),
+        // Rule 5: Unsafe isolation
+        lint("unsafe_code", Level::Forbid),
+        // Rule 7: Exhaustive matching
+        lint("clippy::wildcard_enum_match_arm", Level::Deny),
+        // Additional strictness
+        lint("clippy::as_conversions", Level::Warn),
+        lint("clippy::explicit_iter_loop", Level::Warn),
+        lint("clippy::manual_filter_map", Level::Warn),
+        lint("clippy::manual_find_map", Level::Warn),
+        lint("clippy::needless_range_loop", Level::Warn),
+        lint("clippy::must_use_candidate", Level::Warn),
+        lint("missing_docs", Level::Warn),
+        lint("clippy::missing_errors_doc", Level::Warn),
+        lint("clippy::missing_panics_doc", Level::Warn),
+        lint("clippy::pedantic", Level::Warn),
+        // Allowed (too noisy)
+        lint("clippy::module_name_repetitions", Level::Allow),
+        lint("clippy::too_many_lines", Level::Allow),
+        lint("clippy::needless_lifetimes", Level::Allow),
+    ];
+
+    /// A numbered Grit rule, as referenced in docs ("Rule 11").
+    ///
+    /// Rule 11: `#[non_exhaustive]`, since new rules may be added.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[non_exhaustive]
+    pub enum GritRule {
+        /// Rule 1: Explicit lifetimes on public APIs.
+        ExplicitLifetimes,
+        /// Rule 2: Explicit conversions instead of Deref coercion.
+        ExplicitConversions,
+        /// Rule 3: No panics in library code.
+        NoPanics,
+        /// Rule 4: No type erasure.
+        NoTypeErasure,
+        /// Rule 5: Unsafe isolation.
+        UnsafeIsolation,
+        /// Rule 6: Owned types in async signatures.
+        OwnedAsyncSignatures,
+        /// Rule 7: Exhaustive matching.
+        ExhaustiveMatching,
+        /// Rule 8: Standard error pattern (thiserror).
+        StandardErrorPattern,
+        /// Rule 9: Prefer iterators over loops.
+        PreferIterators,
+        /// Rule 10: Single async runtime (tokio).
+        SingleAsyncRuntime,
+        /// Rule 11: `#[non_exhaustive]` on public enums.
+        NonExhaustiveEnums,
+        /// Rule 12: `#[must_use]` on pure functions.
+        MustUse,
+    }
+
+    impl GritRule {
+        /// Every rule, in numeric order.
+        pub const ALL: [GritRule; 12] = [
+            GritRule::ExplicitLifetimes,
+            GritRule::ExplicitConversions,
+            GritRule::NoPanics,
+            GritRule::NoTypeErasure,
+            GritRule::UnsafeIsolation,
+            GritRule::OwnedAsyncSignatures,
+            GritRule::ExhaustiveMatching,
+            GritRule::StandardErrorPattern,
+            GritRule::PreferIterators,
+            GritRule::SingleAsyncRuntime,
+            GritRule::NonExhaustiveEnums,
+            GritRule::MustUse,
+        ];
+
+        /// Returns the rule number used in the docs.
+        #[must_use]
+        pub const fn number(self) -> u8 {
+            match self {
+                GritRule::ExplicitLifetimes => 1,
+                GritRule::ExplicitConversions => 2,
+                GritRule::NoPanics => 3,
+                GritRule::NoTypeErasure => 4,
+                GritRule::UnsafeIsolation => 5,
+                GritRule::OwnedAsyncSignatures => 6,
+                GritRule::ExhaustiveMatching => 7,
+                GritRule::StandardErrorPattern => 8,
+                GritRule::PreferIterators => 9,
+                GritRule::SingleAsyncRuntime => 10,
+                GritRule::NonExhaustiveEnums => 11,
+                GritRule::MustUse => 12,
+            }
+        }
+
+        /// Returns the rule title as written in the README.
+        #[must_use]
+        pub const fn title(self) -> &'static str {
+            match self {
+                GritRule::ExplicitLifetimes => "Explicit Lifetimes",
+                GritRule::ExplicitConversions => "Explicit Conversions",
+                GritRule::NoPanics => "No Panic in Libraries",
+                GritRule::NoTypeErasure => "No Type Erasure",
+                GritRule::UnsafeIsolation => "Unsafe Isolation",
+                GritRule::OwnedAsyncSignatures => "Owned Async Signatures",
+                GritRule::ExhaustiveMatching => "Exhaustive Matching",
+                GritRule::StandardErrorPattern => "Standard Error Pattern",
+                GritRule::PreferIterators => "Prefer Iterators Over Loops",
+                GritRule::SingleAsyncRuntime => "Single Async Runtime",
+                GritRule::NonExhaustiveEnums => "#[non_exhaustive] on Public Enums",
+                GritRule::MustUse => "#[must_use] on Pure Functions",
+            }
+        }
+
+        /// Returns the lints in `GRIT_LINTS` that enforce this rule.
+        ///
+        /// Empty for rules enforced by code review only.
+        #[must_use]
+        pub const fn lints(self) -> &'static [&'static str] {
+            match self {
+                GritRule::ExplicitLifetimes => &["elided_lifetimes_in_paths"],
+                GritRule::ExplicitConversions => &["clippy::as_conversions"],
+                GritRule::NoPanics => &[
+                    "clippy::unwrap_used",
+                    "clippy::expect_used",
+                    "clippy::panic",
+                    "clippy::indexing_slicing",
+                ],
+                GritRule::UnsafeIsolation => &["unsafe_code"],
+                GritRule::ExhaustiveMatching => &["clippy::wildcard_enum_match_arm"],
+                GritRule::PreferIterators => &[
+                    "clippy::explicit_iter_loop",
+                    "clippy::manual_filter_map",
+                    "clippy::manual_find_map",
+                    "clippy::needless_range_loop",
+                ],
+                GritRule::MustUse => &["clippy::must_use_candidate"],
+                // EXPLICIT: no lint available, enforced via code review
+                GritRule::NoTypeErasure
+                | GritRule::OwnedAsyncSignatures
+                | GritRule::StandardErrorPattern
+                | GritRule::SingleAsyncRuntime
+                | GritRule::NonExhaustiveEnums => &[],
+            }
+        }
+    }
+
+    /// A before/after pair illustrating one rule, condensed from `examples/`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct RuleExample {
+        /// The rule being illustrated.
+        pub rule: GritRule,
+        /// Code violating the rule (from `before.rs`).
+        pub before: &'static str,
+        /// The Grit-compliant rewrite (from `after.rs`).
+        pub after: &'static str,
+    }
+
+    const fn example(rule: GritRule, before: &'static str, after: &'static str) -> RuleExample {
+        RuleExample {
+            rule,
+            before,
+            after,
+        }
+    }
+
+    const EXAMPLES: &[RuleExample] = &[
+        example(
+            GritRule::ExplicitLifetimes,
+            "pub fn parse(input: &str) -> Option<&str>",
+            "pub fn parse<'a>(input: &'a str) -> Option<&'a str>",
+        ),
+        example(
+            GritRule::ExplicitConversions,
+            "process_str(&string);",
+            "process_str(string.as_str());",
+        ),
+        example(
+            GritRule::NoPanics,
+            "users.get(id).unwrap()",
+            "users.get(id).ok_or(UserError::NotFound)",
+        ),
+        example(
+            GritRule::NoTypeErasure,
+            "fn process_dynamic(handler: Box<dyn Fn()>)",
+            "fn process_static<F: Fn()>(handler: F)",
+        ),
+        example(
+            GritRule::UnsafeIsolation,
+            "unsafe { std::ptr::read(data.as_ptr() as *const u32) }",
+            "// SAFETY: length and alignment checked above\n\
+             unsafe { unsafe_ops::read_u32(data) }",
+        ),
+        example(
+            GritRule::OwnedAsyncSignatures,
+            "async fn fetch_user_by_ref(&self, id: &str) -> User",
+            "async fn fetch_user(repo: R, id: String) -> Result<User, UserError>",
+        ),
+        example(
+            GritRule::ExhaustiveMatching,
+            "Status::Active => handle(),\n_ => {}",
+            "Status::Active => handle(),\n\
+             Status::Pending | Status::Cancelled | Status::Expired => {} // EXPLICIT: no action",
+        ),
+        example(
+            GritRule::StandardErrorPattern,
+            "impl std::fmt::Display for ServiceError { /* hand-written */ }",
+            "#[derive(Debug, thiserror::Error)]\n\
+             pub enum ServiceError {\n    #[error(\"IO error: {0}\")]\n    Io(#[from] std::io::Error),\n}",
+        ),
+        example(
+            GritRule::PreferIterators,
+            "for i in 0..items.len() { process(&items[i]); }",
+            "items.iter().for_each(process);",
+        ),
+        example(
+            GritRule::SingleAsyncRuntime,
+            "use async_std::fs;\nuse tokio::time;",
+            "use tokio::{fs, time};",
+        ),
+        example(
+            GritRule::NonExhaustiveEnums,
+            "pub enum ApiError { NotFound, Unauthorized }",
+            "#[non_exhaustive]\npub enum ApiError { NotFound, Unauthorized }",
+        ),
+        example(
+            GritRule::MustUse,
+            "pub fn validate_input(input: &str) -> bool",
+            "#[must_use]\npub fn validate_input(input: &str) -> bool",
+        ),
+    ];
+
+    /// Returns the before/after examples, at least one per `GritRule`.
+    ///
+    /// Shared source for docs and tooling that explain a rule.
+    #[must_use]
+    pub fn all_examples() -> &'static [RuleExample] {
+        EXAMPLES
+    }
+
+    /// Renders `GRIT_LINTS` as rustc flags for `RUSTFLAGS`, e.g. `"-Dclippy::unwrap_used"`.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn rustflags(mode: CrateMode) -> Vec<String> {
+        GRIT_LINTS
+            .iter()
+            .map(|lint| format!("{}{}", lint.level_for(mode).flag(), lint.name))
+            .collect()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[cfg(feature = "std")]
+        #[test]
+        fn rustflags_render_levels_and_binary_mode() {
+            let library = rustflags(CrateMode::Library);
+            assert!(library.contains(&"-Funsafe_code".to_owned()));
+            assert!(library.contains(&"-Dclippy::unwrap_used".to_owned()));
+
+            let binary = rustflags(CrateMode::Binary);
+            for lint in ["unwrap_used", "expect_used", "panic"] {
+                assert!(binary.contains(&format!("-Aclippy::{lint}")), "{lint}");
+            }
+            assert!(binary.contains(&"-Dclippy::indexing_slicing".to_owned()));
+        }
+
+        #[test]
+        fn no_panics_rule_lists_unwrap_used() {
+            assert!(GritRule::NoPanics.lints().contains(&"clippy::unwrap_used"));
+            assert_eq!(GritRule::NoPanics.number(), 3);
+        }
+
+        #[test]
+        fn rule_numbers_are_unique() {
+            let mut numbers = GritRule::ALL.map(GritRule::number).to_vec();
+            numbers.sort_unstable();
+            numbers.dedup();
+            assert_eq!(numbers.len(), GritRule::ALL.len());
+        }
+
+        #[test]
+        fn rule_lints_are_configured() {
+            for name in GritRule::ALL.iter().flat_map(|rule| rule.lints()) {
+                assert!(GRIT_LINTS.iter().any(|lint| lint.name == *name), "{name}");
+            }
+        }
+
+        #[test]
+        fn every_rule_has_an_example() {
+            for rule in GritRule::ALL {
+                let example = all_examples().iter().find(|example| example.rule == rule);
+                assert!(example.is_some(), "{rule:?} has no example");
+            }
+        }
+    }
+}
+
+/// Build-script helpers that generate the Grit lint configuration.
+#[cfg(feature = "std")]
+pub mod build {
+    use std::fmt::Write;
+    use std::fs;
+    use std::io;
+    use std::path::{Path, PathBuf};
+
+    use crate::lints::{CrateMode, GRIT_LINTS};
+
+    /// Name of the file written by `emit_lint_module`.
+    pub const LINT_MODULE_FILE: &str = "grit_lints.rs";
+
+    /// Writes `grit_lints.rs` for `mode` into `out_dir` and returns its path.
+    ///
+    /// `include!` cannot inject crate-level `#![...]` attributes, so the file
+    /// defines a `grit_lints!` macro that applies every lint as an outer
+    /// attribute to the items it wraps, typically the crate's `mod`
+    /// declarations. Regenerating keeps the lints in sync with `GRIT_LINTS`
+    /// instead of a copy-pasted header.
+    ///
+    /// ```ignore
+    /// // build.rs
+    /// fn main() -> std::io::Result<()> {
+    ///     let out_dir = std::env::var_os("OUT_DIR").unwrap_or_default();
+    ///     grit::build::emit_lint_module(out_dir.as_ref(), CrateMode::Library)?;
+    ///     Ok(())
+    /// }
+    ///
+    /// // lib.rs
+    /// include!(concat!(env!("OUT_DIR"), "/grit_lints.rs"));
+    /// grit_lints! {
+    ///     pub mod api;
+    ///     mod store;
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the I/O error if the file cannot be written.
+    pub fn emit_lint_module(out_dir: &Path, mode: CrateMode) -> io::Result<PathBuf> {
+        let path = out_dir.join(LINT_MODULE_FILE);
+        fs::write(&path, lint_module_source(mode))?;
+        Ok(path)
+    }
+
+    /// Returns the Rust source that `emit_lint_module` writes.
+    #[must_use]
+    pub fn lint_module_source(mode: CrateMode) -> String {
+        let mut source = format!(
+            "// @generated by grit::build::emit_lint_module ({mode:?}). Do not edit.\n\n\
+             macro_rules! grit_lints {{\n    ($($item:item)*) => {{\n        $(\n"
+        );
+        for lint in GRIT_LINTS {
+            // EXPLICIT: writing to a String cannot fail
+            let _ = writeln!(
+                source,
+                "            #[{}({})]",
+                lint.level_for(mode).attribute(),
+                lint.name
+            );
+        }
+        source.push_str("            $item\n        )*\n    };\n}\n");
+        source
+    }
+
+    #[cfg(test)]
+    #[allow(clippy::unwrap_used)] // Rule 3 applies to library code, not its tests
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn emitted_lint_module_parses_as_rust() {
+            let out_dir = std::env::temp_dir().join(format!("grit-build-{}", std::process::id()));
+            fs::create_dir_all(&out_dir).unwrap();
+            for mode in [CrateMode::Library, CrateMode::Binary] {
+                let path = emit_lint_module(&out_dir, mode).unwrap();
+                let source = fs::read_to_string(&path).unwrap();
+                assert_eq!(source, lint_module_source(mode));
+                syn::parse_file(&source).unwrap();
+            }
+            fs::remove_dir_all(&out_dir).unwrap();
+        }
+    }
+}
+
+/// Allows the Rule 3 panic lints on the wrapped items.
+///
+/// For application entry points and test helpers inside a crate that
+/// otherwise denies `unwrap`/`expect`/`panic!`. Crates that are binaries
+/// throughout can enable the `binary-mode` feature instead.
+///
+/// ```
+/// #![deny(clippy::unwrap_used)]
+///
+/// grit::allow_panics! {
+///     fn port() -> u16 {
+///         "8080".parse().unwrap()
+///     }
+/// }
+///
+/// assert_eq!(port(), 8080);
+/// ```
+#[macro_export]
+macro_rules! allow_panics {
+    ($($item:item)*) => {
+        $(
+            #[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+            $item
+        )*
+    };
+}
+
+/// Checks that a public enum is `#[non_exhaustive]` (Rule 11).
+///
+/// Generates a `compile_fail` doctest containing a match over the listed
+/// variants with no `_` arm. Doctests build as a separate crate, where that
+/// match only fails (with E0004) if the enum is `#[non_exhaustive]`; for an
+/// exhaustive enum it compiles and `cargo test --doc` reports the failure.
+///
+/// Invoke it in the crate that defines the enum, naming the type by a
+/// `crate::` path and listing every variant (a missing variant also causes
+/// E0004 and would hide the problem). The doctest spells the path with the
+/// crate's real name, read from `CARGO_CRATE_NAME`, so the probe keeps
+/// working when this file is copied into a crate with another name; it
+/// needs a Cargo build.
+///
+/// For an exhaustive enum the generated match compiles, so the probe fails:
+///
+/// ```
+/// enum Status { Active, Pending }
+///
+/// fn probe(value: &Status) {
+///     match value {
+///         Status::Active { .. } => {}
+///         Status::Pending { .. } => {}
+///     }
+/// }
+/// # probe(&Status::Active);
+/// # probe(&Status::Pending);
+/// ```
+///
+/// ```ignore
+/// // Passes: `ApiError` is `#[non_exhaustive]`.
+/// crate::assert_non_exhaustive!(crate::api::ApiError { NotFound, Unauthorized });
+///
+/// // Fails: "Test compiled successfully, but it's marked `compile_fail`."
+/// crate::assert_non_exhaustive!(crate::api::Status { Active, Pending, Cancelled, Expired });
+/// ```
+#[macro_export]
+macro_rules! assert_non_exhaustive {
+    (crate $(:: $segment:ident)+ { $($variant:ident),+ $(,)? }) => {
+        $crate::assert_non_exhaustive!(
+            @probe concat!(env!("CARGO_CRATE_NAME"), $("::", stringify!($segment)),+),
+            $($variant),+
+        );
+    };
+    (@probe $path:expr, $($variant:ident),+) => {
+        const _: () = {
+            #[doc = concat!(
+                "```compile_fail,E0004\n",
+                "fn probe(value: &", $path, ") {\n",
+                "    match value {\n",
+                $("        ", $path, "::", stringify!($variant), " { .. } => {}\n",)+
+                "    }\n",
+                "}\n",
+                "```",
+            )]
+            #[allow(dead_code)]
+            struct NonExhaustiveProbe;
+        };
+    };
+}
+
+/// Matches on a `Status`, requiring an arm for every variant (Rule 7).
+///
+/// Arms are `Variant => expression`. A `_` arm is rejected by the macro
+/// grammar, so omitting a variant is always a compile error (E0004) instead
+/// of silently falling through. `Status` is resolved at the call site.
+///
+/// ```
+/// enum Status { Active, Pending, Cancelled, Expired }
+///
+/// fn label(status: &Status) -> &'static str {
+///     grit::match_all_status!(status, {
+///         Active => "active",
+///         Pending => "pending",
+///         Cancelled => "cancelled",
+///         Expired => "expired",
+///     })
+/// }
+/// # assert_eq!(label(&Status::Expired), "expired");
+/// ```
+///
+/// ```compile_fail,E0004
+/// enum Status { Active, Pending, Cancelled, Expired }
+///
+/// fn label(status: &Status) -> &'static str {
+///     grit::match_all_status!(status, {
+///         Active => "active",
+///         Pending => "pending",
+///         Cancelled => "cancelled",
+///     })
+/// }
+/// ```
+#[macro_export]
+macro_rules! match_all_status {
+    ($status:expr, { $($variant:ident => $body:expr),+ $(,)? }) => {
+        match $status {
+            $(Status::$variant => $body,)+
+        }
+    };
 }
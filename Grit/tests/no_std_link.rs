@@ -0,0 +1,25 @@
+//! Link test for the `nostd_core` subset.
+//!
+//! This crate is `#![no_std]`, so it only builds if `nostd_core` needs
+//! nothing beyond `core`. Run it against the lib built without `std`:
+//! `cargo test --no-default-features --test no_std_link`.
+
+#![no_std]
+
+// The libtest harness itself needs std; the code under test does not
+extern crate std;
+
+use grit::nostd_core::{read_u32_be, read_u32_le, split_at_checked, validate_id};
+
+#[test]
+fn nostd_core_links_into_a_no_std_crate() {
+    assert_eq!(validate_id("a"), Ok("a"));
+    assert_eq!(validate_id(""), Err("empty id"));
+    assert_eq!(read_u32_le(&[1, 0, 0, 0]), Ok(1));
+    assert_eq!(read_u32_be(&[0, 0, 0, 1]), Ok(1));
+    assert_eq!(
+        split_at_checked(&[1, 2, 3], 1),
+        Some((&[1][..], &[2, 3][..]))
+    );
+    assert_eq!(split_at_checked(&[1], 2), None);
+}